@@ -1,12 +1,15 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, MaxKittiesOwned,
+	Signature, SubstrateKittiesConfig, SudoConfig, SystemConfig, WASM_BINARY,
 };
+use pallet_kitties::genes;
 use sc_service::ChainType;
+use serde::Deserialize;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{hashing::blake2_128, sr25519, Bytes, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::{collections::BTreeMap, convert::TryFrom, fs::File, path::Path};
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
@@ -36,8 +39,82 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
 }
 
-pub fn development_config() -> Result<ChainSpec, String> {
+/// A kitty entry in a genesis kitties JSON file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisKitty {
+	/// SS58 address of the owner. The owner must be endowed to cover the kitty stake.
+	owner: AccountId,
+	/// The 16 DNA bytes as a `0x`-prefixed hex string.
+	dna: Bytes,
+}
+
+/// Load genesis kitties from a JSON file containing an array of
+/// `{ "owner": "<ss58 address>", "dna": "0x<32 hex digits>" }` objects.
+///
+/// Owners may not hold more kitties than the runtime's `MaxKittiesOwned`.
+pub fn load_genesis_kitties(path: &Path) -> Result<Vec<(AccountId, [u8; 16])>, String> {
+	let file = File::open(path)
+		.map_err(|e| format!("Error opening genesis kitties file `{}`: {}", path.display(), e))?;
+	let kitties: Vec<GenesisKitty> = serde_json::from_reader(file)
+		.map_err(|e| format!("Error parsing genesis kitties file `{}`: {}", path.display(), e))?;
+	let mut owned = BTreeMap::<&AccountId, u32>::new();
+	for kitty in &kitties {
+		let count = owned.entry(&kitty.owner).or_default();
+		*count += 1;
+		if *count > MaxKittiesOwned::get() {
+			return Err(format!(
+				"Genesis kitties file `{}` gives {} more than {} kitties",
+				path.display(),
+				kitty.owner,
+				MaxKittiesOwned::get()
+			))
+		}
+	}
+	kitties
+		.into_iter()
+		.map(|kitty| {
+			let dna = <[u8; 16]>::try_from(&kitty.dna[..]).map_err(|_| {
+				format!("Genesis kitty of {} has {} DNA bytes, expected 16", kitty.owner, kitty.dna.len())
+			})?;
+			Ok((kitty.owner, dna))
+		})
+		.collect()
+}
+
+/// Generation 0 kitties for each owner, with DNA derived from the owner's account so that the
+/// starter set is the same on every run.
+pub fn starter_kitties(owners: &[AccountId], per_owner: u32) -> Vec<(AccountId, [u8; 16])> {
+	owners
+		.iter()
+		.flat_map(|owner| {
+			(0..per_owner).map(move |i| {
+				let seed = blake2_128(&[owner.as_ref(), &i.to_le_bytes()[..]].concat());
+				(owner.clone(), genes::base_dna(seed))
+			})
+		})
+		.collect()
+}
+
+/// Use the kitties from `genesis_kitties` if given, the starter set otherwise.
+fn genesis_kitties_or(
+	genesis_kitties: Option<&Path>,
+	starter: impl FnOnce() -> Vec<(AccountId, [u8; 16])>,
+) -> Result<Vec<(AccountId, [u8; 16])>, String> {
+	genesis_kitties.map_or_else(|| Ok(starter()), load_genesis_kitties)
+}
+
+pub fn development_config(genesis_kitties: Option<&Path>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let kitties = genesis_kitties_or(genesis_kitties, || {
+		starter_kitties(
+			&[
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				get_account_id_from_seed::<sr25519::Public>("Bob"),
+			],
+			3,
+		)
+	})?;
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -59,6 +136,8 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Genesis kitties
+				kitties.clone(),
 				true,
 			)
 		},
@@ -75,8 +154,21 @@ pub fn development_config() -> Result<ChainSpec, String> {
 	))
 }
 
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
+pub fn local_testnet_config(genesis_kitties: Option<&Path>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let kitties = genesis_kitties_or(genesis_kitties, || {
+		starter_kitties(
+			&[
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				get_account_id_from_seed::<sr25519::Public>("Bob"),
+				get_account_id_from_seed::<sr25519::Public>("Charlie"),
+				get_account_id_from_seed::<sr25519::Public>("Dave"),
+				get_account_id_from_seed::<sr25519::Public>("Eve"),
+				get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+			],
+			2,
+		)
+	})?;
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -106,6 +198,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Genesis kitties
+				kitties.clone(),
 				true,
 			)
 		},
@@ -128,6 +222,7 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	kitties: Vec<(AccountId, [u8; 16])>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -151,5 +246,6 @@ fn testnet_genesis(
 			key: root_key,
 		},
 		transaction_payment: Default::default(),
+		substrate_kitties: SubstrateKittiesConfig { kitties },
 	}
 }
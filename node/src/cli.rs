@@ -1,4 +1,5 @@
 use sc_cli::RunCmd;
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -8,6 +9,11 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// JSON file with the genesis kitties of the `dev` and `local` chains, replacing the
+	/// built-in starter set.
+	#[structopt(long, parse(from_os_str))]
+	pub genesis_kitties: Option<PathBuf>,
 }
 
 #[derive(Debug, StructOpt)]
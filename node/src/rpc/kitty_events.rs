@@ -0,0 +1,455 @@
+//! Pub/sub RPC streaming decoded kitty events.
+//!
+//! Subscribers receive the `KittyCreate`, `KittyTransfer`, `KittyListed`, `KittyListedDutch` and
+//! `KittySold` events of every new best block, or of every block finalized by GRANDPA when subscribing with
+//! `finalizedOnly`. Events of a best block may still be reverted by a reorg. Listings and sales
+//! priced in an asset are sent with the asset id, balances are sent as decimal strings.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Decode;
+use frame_system::EventRecord;
+use futures::{future, stream, stream::BoxStream, FutureExt, SinkExt, StreamExt};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use log::warn;
+use node_template_runtime::{
+	opaque::Block, AccountId, AssetId, Balance, BlockNumber, Event, Hash, KittyIndex,
+};
+use pallet_kitties::Event as KittiesEvent;
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sc_rpc::{Metadata, SubscriptionTaskExecutor};
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+
+/// A kitty event decoded from a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "event")]
+pub enum KittyEvent {
+	/// A kitty was created or bred.
+	#[serde(rename_all = "camelCase")]
+	KittyCreate { owner: AccountId, kitty_id: KittyIndex },
+	/// A kitty changed owner outside of a sale.
+	#[serde(rename_all = "camelCase")]
+	KittyTransfer { from: AccountId, to: AccountId, kitty_id: KittyIndex },
+	/// A kitty was listed for sale, or its listing was withdrawn when `price` is `None`.
+	/// `asset` is the asset the price is in, `None` for the native currency.
+	#[serde(rename_all = "camelCase")]
+	KittyListed {
+		owner: AccountId,
+		kitty_id: KittyIndex,
+		#[serde(with = "pallet_kitties::serde_balance::option")]
+		price: Option<Balance>,
+		asset: Option<AssetId>,
+	},
+	/// A kitty was listed in a Dutch auction, its price falls from `start_price` to `end_price`
+	/// over `duration` blocks.
+	#[serde(rename_all = "camelCase")]
+	KittyListedDutch {
+		owner: AccountId,
+		kitty_id: KittyIndex,
+		#[serde(with = "pallet_kitties::serde_balance")]
+		start_price: Balance,
+		#[serde(with = "pallet_kitties::serde_balance")]
+		end_price: Balance,
+		duration: BlockNumber,
+	},
+	/// A kitty was sold. `asset` is the asset it was paid in, `None` for the native currency.
+	#[serde(rename_all = "camelCase")]
+	KittySold {
+		buyer: AccountId,
+		seller: AccountId,
+		kitty_id: KittyIndex,
+		#[serde(with = "pallet_kitties::serde_balance")]
+		price: Balance,
+		#[serde(with = "pallet_kitties::serde_balance")]
+		fee: Balance,
+		#[serde(with = "pallet_kitties::serde_balance")]
+		royalty: Balance,
+		asset: Option<AssetId>,
+	},
+}
+
+impl KittyEvent {
+	/// Decode a runtime event, `None` if it is not one of the streamed kitty events.
+	pub fn from_runtime(event: Event) -> Option<Self> {
+		match event {
+			Event::SubstrateKitties(KittiesEvent::KittyCreate(owner, kitty_id)) =>
+				Some(KittyEvent::KittyCreate { owner, kitty_id }),
+			Event::SubstrateKitties(KittiesEvent::KittyTransfer(from, to, kitty_id)) =>
+				Some(KittyEvent::KittyTransfer { from, to, kitty_id }),
+			Event::SubstrateKitties(KittiesEvent::KittyListed(owner, kitty_id, price)) =>
+				Some(KittyEvent::KittyListed { owner, kitty_id, price, asset: None }),
+			Event::SubstrateKitties(KittiesEvent::KittyListedForAsset(owner, kitty_id, asset, price)) =>
+				Some(KittyEvent::KittyListed { owner, kitty_id, price: Some(price), asset: Some(asset) }),
+			Event::SubstrateKitties(KittiesEvent::KittyListedDutch(
+				owner,
+				kitty_id,
+				start_price,
+				end_price,
+				duration,
+			)) => Some(KittyEvent::KittyListedDutch { owner, kitty_id, start_price, end_price, duration }),
+			Event::SubstrateKitties(KittiesEvent::KittySold(
+				buyer,
+				seller,
+				kitty_id,
+				price,
+				fee,
+				royalty,
+			)) => Some(KittyEvent::KittySold { buyer, seller, kitty_id, price, fee, royalty, asset: None }),
+			Event::SubstrateKitties(KittiesEvent::KittySoldForAsset(
+				buyer,
+				seller,
+				kitty_id,
+				asset,
+				price,
+				fee,
+				royalty,
+			)) => Some(KittyEvent::KittySold {
+				buyer,
+				seller,
+				kitty_id,
+				price,
+				fee,
+				royalty,
+				asset: Some(asset),
+			}),
+			_ => None,
+		}
+	}
+
+	/// The kitty the event is about.
+	pub fn kitty_id(&self) -> KittyIndex {
+		match self {
+			KittyEvent::KittyCreate { kitty_id, .. } |
+			KittyEvent::KittyTransfer { kitty_id, .. } |
+			KittyEvent::KittyListed { kitty_id, .. } |
+			KittyEvent::KittyListedDutch { kitty_id, .. } |
+			KittyEvent::KittySold { kitty_id, .. } => *kitty_id,
+		}
+	}
+
+	/// Whether `account` takes part in the event.
+	pub fn involves(&self, account: &AccountId) -> bool {
+		match self {
+			KittyEvent::KittyCreate { owner, .. } |
+			KittyEvent::KittyListed { owner, .. } |
+			KittyEvent::KittyListedDutch { owner, .. } => owner == account,
+			KittyEvent::KittyTransfer { from, to, .. } => from == account || to == account,
+			KittyEvent::KittySold { buyer, seller, .. } => buyer == account || seller == account,
+		}
+	}
+}
+
+/// A kitty event with the block it was emitted in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KittyEventNotification {
+	/// Hash of the block that emitted the event.
+	pub block_hash: Hash,
+	/// Number of the block that emitted the event.
+	pub block_number: BlockNumber,
+	/// Whether the block was finalized when the event was sent.
+	pub finalized: bool,
+	/// The event itself.
+	#[serde(flatten)]
+	pub event: KittyEvent,
+}
+
+/// Which events a subscription receives. Without an account or kitty id every event is sent.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct KittyEventFilter {
+	/// Only events the account takes part in.
+	pub account: Option<AccountId>,
+	/// Only events about the kitty.
+	pub kitty_id: Option<KittyIndex>,
+	/// Only send events once their block is finalized by GRANDPA.
+	#[serde(default)]
+	pub finalized_only: bool,
+}
+
+impl KittyEventFilter {
+	/// Whether the event passes the account and kitty id filters.
+	pub fn matches(&self, event: &KittyEvent) -> bool {
+		self.account.as_ref().map_or(true, |account| event.involves(account)) &&
+			self.kitty_id.map_or(true, |kitty_id| event.kitty_id() == kitty_id)
+	}
+}
+
+/// Kitty event subscriptions.
+#[rpc(server)]
+pub trait KittyEventsApi {
+	/// RPC metadata
+	type Metadata;
+
+	/// Subscribe to kitty events, optionally filtered by account or kitty id.
+	#[pubsub(subscription = "kitties_events", subscribe, name = "kitties_subscribeEvents")]
+	fn subscribe_events(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<KittyEventNotification>,
+		filter: Option<KittyEventFilter>,
+	);
+
+	/// Unsubscribe from kitty events.
+	#[pubsub(subscription = "kitties_events", unsubscribe, name = "kitties_unsubscribeEvents")]
+	fn unsubscribe_events(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool>;
+}
+
+/// Streams kitty events of imported or finalized blocks to subscribers.
+pub struct KittyEvents<C, B> {
+	client: Arc<C>,
+	subscriptions: SubscriptionManager,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> KittyEvents<C, B> {
+	/// Create new `KittyEvents` with the given reference to the client.
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, subscriptions: SubscriptionManager::new(Arc::new(executor)), _marker: PhantomData }
+	}
+}
+
+impl<C, B> KittyEventsApi for KittyEvents<C, B>
+where
+	B: Backend<Block> + Send + Sync + 'static,
+	C: BlockchainEvents<Block> + HeaderBackend<Block> + StorageProvider<Block, B>,
+	C: Send + Sync + 'static,
+{
+	type Metadata = Metadata;
+
+	fn subscribe_events(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<KittyEventNotification>,
+		filter: Option<KittyEventFilter>,
+	) {
+		let filter = filter.unwrap_or_default();
+		let finalized = filter.finalized_only;
+		let blocks =
+			if finalized { finalized_blocks(&self.client) } else { best_blocks(&*self.client) };
+		let client = self.client.clone();
+
+		self.subscriptions.add(subscriber, move |sink| {
+			blocks
+				.flat_map(move |(block_hash, block_number)| {
+					let notifications = block_events(&*client, block_hash)
+						.into_iter()
+						.filter_map(KittyEvent::from_runtime)
+						.filter(|event| filter.matches(event))
+						.map(|event| {
+							Ok::<_, ()>(Ok(KittyEventNotification {
+								block_hash,
+								block_number,
+								finalized,
+								event,
+							}))
+						})
+						.collect::<Vec<_>>();
+					stream::iter(notifications)
+				})
+				.forward(sink.sink_map_err(|e| warn!("Error sending kitty events: {:?}", e)))
+				.map(drop)
+		});
+	}
+
+	fn unsubscribe_events(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
+}
+
+/// Every block that becomes the new best block.
+fn best_blocks<C>(client: &C) -> BoxStream<'static, (Hash, BlockNumber)>
+where
+	C: BlockchainEvents<Block>,
+{
+	client
+		.import_notification_stream()
+		.filter(|notification| future::ready(notification.is_new_best))
+		.map(|notification| (notification.hash, *notification.header.number()))
+		.boxed()
+}
+
+/// Every finalized block in order, starting after the block finalized at subscription time.
+fn finalized_blocks<C>(client: &Arc<C>) -> BoxStream<'static, (Hash, BlockNumber)>
+where
+	C: BlockchainEvents<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+{
+	let notifications = client.finality_notification_stream();
+	let client = client.clone();
+	let mut last_finalized = client.info().finalized_number;
+	notifications
+		.flat_map(move |notification| {
+			// GRANDPA can finalize several blocks at once, send the events of all of them.
+			let number = *notification.header.number();
+			let blocks = (last_finalized + 1..=number)
+				.filter_map(|n| client.hash(n).ok().flatten().map(|hash| (hash, n)))
+				.collect::<Vec<_>>();
+			last_finalized = last_finalized.max(number);
+			stream::iter(blocks)
+		})
+		.boxed()
+}
+
+/// The runtime events deposited in a block.
+fn block_events<C, B>(client: &C, block_hash: Hash) -> Vec<Event>
+where
+	B: Backend<Block>,
+	C: StorageProvider<Block, B>,
+{
+	let key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+	match client.storage(&BlockId::Hash(block_hash), &key) {
+		Ok(Some(data)) => match Vec::<EventRecord<Event, Hash>>::decode(&mut &data.0[..]) {
+			Ok(records) => records.into_iter().map(|record| record.event).collect(),
+			Err(e) => {
+				warn!("Unable to decode events of block {}: {:?}", block_hash, e);
+				Vec::new()
+			},
+		},
+		Ok(None) => Vec::new(),
+		Err(e) => {
+			warn!("Unable to read events of block {}: {:?}", block_hash, e);
+			Vec::new()
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn alice() -> AccountId {
+		AccountId::new([1; 32])
+	}
+
+	fn bob() -> AccountId {
+		AccountId::new([2; 32])
+	}
+
+	fn kitties_event(event: KittiesEvent<node_template_runtime::Runtime>) -> Option<KittyEvent> {
+		KittyEvent::from_runtime(Event::SubstrateKitties(event))
+	}
+
+	#[test]
+	fn from_runtime_decodes_created_and_transferred() {
+		assert_eq!(
+			kitties_event(KittiesEvent::KittyCreate(alice(), 1)),
+			Some(KittyEvent::KittyCreate { owner: alice(), kitty_id: 1 })
+		);
+		assert_eq!(
+			kitties_event(KittiesEvent::KittyTransfer(alice(), bob(), 1)),
+			Some(KittyEvent::KittyTransfer { from: alice(), to: bob(), kitty_id: 1 })
+		);
+	}
+
+	#[test]
+	fn from_runtime_decodes_listings() {
+		assert_eq!(
+			kitties_event(KittiesEvent::KittyListed(alice(), 1, Some(500))),
+			Some(KittyEvent::KittyListed { owner: alice(), kitty_id: 1, price: Some(500), asset: None })
+		);
+		assert_eq!(
+			kitties_event(KittiesEvent::KittyListed(alice(), 1, None)),
+			Some(KittyEvent::KittyListed { owner: alice(), kitty_id: 1, price: None, asset: None })
+		);
+		assert_eq!(
+			kitties_event(KittiesEvent::KittyListedForAsset(alice(), 1, 7, 500)),
+			Some(KittyEvent::KittyListed { owner: alice(), kitty_id: 1, price: Some(500), asset: Some(7) })
+		);
+		assert_eq!(
+			kitties_event(KittiesEvent::KittyListedDutch(alice(), 1, 1_000, 100, 10)),
+			Some(KittyEvent::KittyListedDutch {
+				owner: alice(),
+				kitty_id: 1,
+				start_price: 1_000,
+				end_price: 100,
+				duration: 10,
+			})
+		);
+	}
+
+	#[test]
+	fn from_runtime_decodes_sales() {
+		assert_eq!(
+			kitties_event(KittiesEvent::KittySold(bob(), alice(), 1, 500, 50, 25)),
+			Some(KittyEvent::KittySold {
+				buyer: bob(),
+				seller: alice(),
+				kitty_id: 1,
+				price: 500,
+				fee: 50,
+				royalty: 25,
+				asset: None,
+			})
+		);
+		assert_eq!(
+			kitties_event(KittiesEvent::KittySoldForAsset(bob(), alice(), 1, 7, 500, 50, 25)),
+			Some(KittyEvent::KittySold {
+				buyer: bob(),
+				seller: alice(),
+				kitty_id: 1,
+				price: 500,
+				fee: 50,
+				royalty: 25,
+				asset: Some(7),
+			})
+		);
+	}
+
+	#[test]
+	fn from_runtime_skips_other_events() {
+		assert_eq!(kitties_event(KittiesEvent::KittyBurned(alice(), 1)), None);
+		assert_eq!(kitties_event(KittiesEvent::ListingExpired(1)), None);
+	}
+
+	#[test]
+	fn filter_matches_account() {
+		let filter = KittyEventFilter { account: Some(bob()), ..Default::default() };
+		assert!(filter.matches(&KittyEvent::KittyTransfer { from: alice(), to: bob(), kitty_id: 1 }));
+		assert!(filter.matches(&KittyEvent::KittyListedDutch {
+			owner: bob(),
+			kitty_id: 1,
+			start_price: 1_000,
+			end_price: 100,
+			duration: 10,
+		}));
+		assert!(!filter.matches(&KittyEvent::KittyCreate { owner: alice(), kitty_id: 1 }));
+		assert!(!filter.matches(&KittyEvent::KittyListed {
+			owner: alice(),
+			kitty_id: 1,
+			price: Some(500),
+			asset: None,
+		}));
+	}
+
+	#[test]
+	fn filter_matches_kitty_id() {
+		let filter = KittyEventFilter { kitty_id: Some(2), ..Default::default() };
+		assert!(filter.matches(&KittyEvent::KittyCreate { owner: alice(), kitty_id: 2 }));
+		assert!(!filter.matches(&KittyEvent::KittyCreate { owner: alice(), kitty_id: 1 }));
+		// With both an account and a kitty id the event has to match both.
+		let filter = KittyEventFilter { account: Some(alice()), kitty_id: Some(2), ..Default::default() };
+		assert!(filter.matches(&KittyEvent::KittyCreate { owner: alice(), kitty_id: 2 }));
+		assert!(!filter.matches(&KittyEvent::KittyCreate { owner: bob(), kitty_id: 2 }));
+	}
+
+	#[test]
+	fn filter_deserializes_camel_case() {
+		let filter: KittyEventFilter = serde_json::from_str(r#"{"kittyId": 2, "finalizedOnly": true}"#).unwrap();
+		assert_eq!(filter.kitty_id, Some(2));
+		assert!(filter.account.is_none());
+		assert!(filter.finalized_only);
+	}
+}
@@ -0,0 +1,144 @@
+//! RPC interface for the kitties pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use pallet_kitties_runtime_api::KittiesApi as KittiesRuntimeApi;
+use pallet_kitties_runtime_api::{KittyInfo, KittyTraits};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+#[rpc]
+pub trait KittiesApi<BlockHash, AccountId, KittyIndex, Balance, BlockNumber, AssetId> {
+	/// A kitty with its owner, decoded traits and sale state.
+	#[rpc(name = "kitties_get")]
+	fn get(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId>>>;
+
+	/// The ids of the kitties owned by an account.
+	#[rpc(name = "kitties_ownedBy")]
+	fn owned_by(&self, account: AccountId, at: Option<BlockHash>) -> Result<Vec<KittyIndex>>;
+
+	/// One page of the kitties for sale, starting at page 0.
+	#[rpc(name = "kitties_listings")]
+	fn listings(
+		&self,
+		page: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId>>>;
+
+	/// The number of kitties created so far, including burned ones.
+	#[rpc(name = "kitties_count")]
+	fn count(&self, at: Option<BlockHash>) -> Result<KittyIndex>;
+
+	/// The traits decoded from a kitty's DNA.
+	#[rpc(name = "kitties_traits")]
+	fn traits(&self, kitty_id: KittyIndex, at: Option<BlockHash>) -> Result<Option<KittyTraits>>;
+
+	/// The number of living kitties expressing each allele of a gene.
+	#[rpc(name = "kitties_traitCounts")]
+	fn trait_counts(&self, gene: u8, at: Option<BlockHash>) -> Result<Vec<u32>>;
+
+	/// The rarity score of a kitty, higher is rarer.
+	#[rpc(name = "kitties_rarityScore")]
+	fn rarity_score(&self, kitty_id: KittyIndex, at: Option<BlockHash>) -> Result<Option<u32>>;
+}
+
+/// Provides RPC methods to query kitties.
+pub struct Kitties<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Kitties<C, Block> {
+	/// Create new `Kitties` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code for failures when calling into the runtime.
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query kitties.".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, AccountId, KittyIndex, Balance, BlockNumber, AssetId>
+	KittiesApi<<Block as BlockT>::Hash, AccountId, KittyIndex, Balance, BlockNumber, AssetId>
+	for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: KittiesRuntimeApi<Block, AccountId, KittyIndex, Balance, BlockNumber, AssetId>,
+	AccountId: Codec,
+	KittyIndex: Codec,
+	Balance: Codec,
+	BlockNumber: Codec,
+	AssetId: Codec,
+{
+	fn get(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().kitty(&at, kitty_id).map_err(runtime_error)
+	}
+
+	fn owned_by(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<KittyIndex>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().kitties_owned_by(&at, account).map_err(runtime_error)
+	}
+
+	fn listings(
+		&self,
+		page: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().listings(&at, page).map_err(runtime_error)
+	}
+
+	fn count(&self, at: Option<<Block as BlockT>::Hash>) -> Result<KittyIndex> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().kitty_count(&at).map_err(runtime_error)
+	}
+
+	fn traits(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<KittyTraits>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().kitty_traits(&at, kitty_id).map_err(runtime_error)
+	}
+
+	fn trait_counts(&self, gene: u8, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u32>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().trait_counts(&at, gene).map_err(runtime_error)
+	}
+
+	fn rarity_score(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<u32>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().rarity_score(&at, kitty_id).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,32 @@
+//! Runtime API definition for the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use pallet_kitties::{genes::KittyTraits, KittyInfo};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<AccountId, KittyIndex, Balance, BlockNumber, AssetId> where
+		AccountId: Codec,
+		KittyIndex: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		AssetId: Codec,
+	{
+		/// The traits decoded from a kitty's DNA, or `None` if the kitty does not exist.
+		fn kitty_traits(kitty_id: KittyIndex) -> Option<KittyTraits>;
+		/// The number of living kitties expressing each allele (0-15) of a gene.
+		fn trait_counts(gene: u8) -> Vec<u32>;
+		/// The rarity score of a kitty, or `None` if the kitty does not exist.
+		fn rarity_score(kitty_id: KittyIndex) -> Option<u32>;
+		/// A kitty with its owner, decoded traits and sale state, or `None` if it does not exist.
+		fn kitty(kitty_id: KittyIndex) -> Option<KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId>>;
+		/// The ids of the kitties owned by an account.
+		fn kitties_owned_by(account: AccountId) -> Vec<KittyIndex>;
+		/// One page of the kitties for sale, starting at page 0.
+		fn listings(page: u32) -> Vec<KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId>>;
+		/// The number of kitties created so far, including burned ones.
+		fn kitty_count() -> KittyIndex;
+	}
+}
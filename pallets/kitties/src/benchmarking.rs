@@ -0,0 +1,697 @@
+//! Benchmarking setup for pallet-kitties
+//!
+//! Every benchmark starts from the worst case its extrinsic can meet: the accounts involved own as
+//! many kitties as allowed and the kitty has every listing that has to be cleared.
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as SubstrateKitties;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	sp_runtime::traits::Bounded,
+	sp_std::{convert::TryFrom, vec::Vec},
+	traits::{fungibles, Currency, Get},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_io::hashing::blake2_128;
+
+const SEED: u32 = 0;
+
+// 为账户充值足够质押和购买的余额
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 8u32.into());
+}
+
+fn funded_account<T: Config>(name: &'static str) -> T::AccountId {
+	let who = account(name, 0, SEED);
+	fund::<T>(&who);
+	who
+}
+
+// 为账户创建n个Kitty，返回它们的ID
+fn create_kitties<T: Config>(owner: &T::AccountId, n: u32) -> Result<Vec<T::KittyIndex>, &'static str> {
+	(0..n)
+		.map(|i| -> Result<T::KittyIndex, &'static str> {
+			let kitty_id = SubstrateKitties::<T>::kitty_cnt().unwrap_or_else(|| 0u32.into());
+			let dna = genes::base_dna((owner, i).using_encoded(blake2_128));
+			SubstrateKitties::<T>::create_kitty_with_stake(owner, dna, None, 0)?;
+			Ok(kitty_id)
+		})
+		.collect()
+}
+
+fn set_gender<T: Config>(kitty_id: T::KittyIndex, gender: Gender) {
+	Kitties::<T>::mutate(kitty_id, |kitty| {
+		if let Some(kitty) = kitty {
+			kitty.gender = gender;
+		}
+	});
+}
+
+// 只剩一个空位的区块队列，填充的是其他Kitty的ID
+fn almost_full_queue<T: Config, S: Get<u32>>() -> Result<BoundedVec<T::KittyIndex, S>, &'static str> {
+	let ids: Vec<T::KittyIndex> =
+		(0..S::get().saturating_sub(1)).map(|i| T::KittyIndex::max_value() - i.into()).collect();
+	BoundedVec::try_from(ids).map_err(|_| "queue too long")
+}
+
+// 给Kitty挂一个带失效区块的出售单，所在的失效队列已满
+fn list_with_full_expiry_queue<T: Config>(owner: &T::AccountId, kitty_id: T::KittyIndex) -> Result<(), &'static str> {
+	let expires = frame_system::Pallet::<T>::block_number() + 10u32.into();
+	ListingsExpiringAt::<T>::insert(expires, almost_full_queue::<T, T::MaxListingsPerBlock>()?);
+	SubstrateKitties::<T>::sell_with_expiry(
+		RawOrigin::Signed(owner.clone()).into(),
+		kitty_id,
+		T::StakeForEachKitty::get(),
+		expires,
+	)?;
+	Ok(())
+}
+
+// 给Kitty加上所有者变更时需要清除的授权和出租
+fn add_approval_and_rentals<T: Config>(owner: &T::AccountId, kitty_id: T::KittyIndex) -> Result<(), &'static str> {
+	let origin: <T as frame_system::Config>::Origin = RawOrigin::Signed(owner.clone()).into();
+	SubstrateKitties::<T>::approve(origin.clone(), account("approved", 0, SEED), kitty_id)?;
+	SubstrateKitties::<T>::offer_sire(origin.clone(), kitty_id, Some(1u32.into()))?;
+	SubstrateKitties::<T>::list_for_rent(origin, kitty_id, 1u32.into(), 10u32.into())?;
+	Ok(())
+}
+
+// 让Kitty处于冷却期
+fn set_cooldown<T: Config>(kitty_id: T::KittyIndex) {
+	let ready_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+	BreedingStates::<T>::insert(kitty_id, BreedingState { breed_count: 1, ready_at });
+	CooldownEnds::<T>::insert(ready_at, kitty_id, ());
+}
+
+// 使下一个承诺的揭示区块和失效区块只剩free个空位
+fn fill_reveal_queue<T: Config>(free: u32) -> Result<(), &'static str> {
+	let reveal_at = frame_system::Pallet::<T>::block_number() + T::RevealDelay::get();
+	let others = T::MaxCommitmentsPerBlock::get().saturating_sub(free);
+	let ids: Vec<u64> = (0..others).map(|i| u64::max_value() - i as u64).collect();
+	let queue = BoundedVec::try_from(ids).map_err(|_| "reveal queue too long")?;
+	CommitmentsRevealingAt::<T>::insert(reveal_at + 1u32.into(), queue.clone());
+	CommitmentsExpiringAt::<T>::insert(reveal_at + T::RevealWindow::get(), queue);
+	Ok(())
+}
+
+// 创建可以购买Kitty的资产，并为买家铸造足够的余额
+fn create_asset<T: Config>(admin: &T::AccountId, buyer: &T::AccountId) -> Result<AssetIdOf<T>, &'static str>
+where
+	T::Assets: fungibles::Create<T::AccountId> + fungibles::Mutate<T::AccountId>,
+	AssetIdOf<T>: From<u32>,
+{
+	let asset_id: AssetIdOf<T> = 0u32.into();
+	<T::Assets as fungibles::Create<T::AccountId>>::create(asset_id, admin.clone(), true, 1u32.into())?;
+	<T::Assets as fungibles::Mutate<T::AccountId>>::mint_into(
+		asset_id,
+		buyer,
+		BalanceOf::<T>::max_value() / 8u32.into(),
+	)?;
+	Ok(asset_id)
+}
+
+// 种猫的所有者出租一个种猫，返回种猫的ID
+fn offer_sire_of<T: Config>(sire_owner: &T::AccountId) -> Result<T::KittyIndex, &'static str> {
+	let sire_id = create_kitties::<T>(sire_owner, 1)?[0];
+	set_gender::<T>(sire_id, Gender::Male);
+	SubstrateKitties::<T>::offer_sire(
+		RawOrigin::Signed(sire_owner.clone()).into(),
+		sire_id,
+		Some(T::StakeForEachKitty::get()),
+	)?;
+	Ok(sire_id)
+}
+
+// n个买家分别对Kitty报价，返回这些买家
+fn make_offers<T: Config>(
+	kitty_id: T::KittyIndex,
+	n: u32,
+	expires: T::BlockNumber,
+) -> Result<Vec<T::AccountId>, &'static str> {
+	(0..n)
+		.map(|i| -> Result<T::AccountId, &'static str> {
+			let buyer: T::AccountId = account("buyer", i, SEED);
+			fund::<T>(&buyer);
+			let amount: BalanceOf<T> = T::StakeForEachKitty::get();
+			SubstrateKitties::<T>::make_offer(RawOrigin::Signed(buyer.clone()).into(), kitty_id, amount, expires)?;
+			Ok(buyer)
+		})
+		.collect()
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T::Assets: fungibles::Create<T::AccountId> + fungibles::Mutate<T::AccountId>,
+			AssetIdOf<T>: From<u32>,
+	}
+
+	create {
+		// 直接创建需要运行时允许即时铸造
+		frame_support::ensure!(T::AllowInstantMint::get(), "instant mint must be enabled to benchmark create");
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 1)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(OwnedKitties::<T>::get(&caller).len() as u32, T::MaxKittiesOwned::get());
+	}
+
+	create_batch {
+		let n in 1 .. T::MaxBatchSize::get();
+		frame_support::ensure!(T::AllowInstantMint::get(), "instant mint must be enabled to benchmark create_batch");
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - n)?;
+	}: _(RawOrigin::Signed(caller.clone()), n)
+	verify {
+		assert_eq!(OwnedKitties::<T>::get(&caller).len() as u32, T::MaxKittiesOwned::get());
+	}
+
+	breed {
+		frame_support::ensure!(T::AllowInstantMint::get(), "instant mint must be enabled to benchmark breed");
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let ids = create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 1)?;
+		let (kitty_id_1, kitty_id_2) = (ids[0], ids[1]);
+		set_gender::<T>(kitty_id_1, Gender::Male);
+		set_gender::<T>(kitty_id_2, Gender::Female);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id_1, kitty_id_2)
+	verify {
+		assert_eq!(OwnedKitties::<T>::get(&caller).len() as u32, T::MaxKittiesOwned::get());
+	}
+
+	breed_with_sire {
+		frame_support::ensure!(T::AllowInstantMint::get(), "instant mint must be enabled to benchmark breed_with_sire");
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 1)?[0];
+		set_gender::<T>(kitty_id, Gender::Female);
+		let sire_id = offer_sire_of::<T>(&funded_account::<T>("sire_owner"))?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, sire_id)
+	verify {
+		assert_eq!(OwnedKitties::<T>::get(&caller).len() as u32, T::MaxKittiesOwned::get());
+	}
+
+	sell {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, T::MaxKittiesOwned::get())?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		let price: BalanceOf<T> = T::StakeForEachKitty::get();
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(price))
+	verify {
+		assert_eq!(ListForSale::<T>::get(kitty_id), Some(price));
+		assert_eq!(ListingExpiry::<T>::get(kitty_id), None);
+	}
+
+	sell_for_asset {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, T::MaxKittiesOwned::get())?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		let asset_id = create_asset::<T>(&caller, &caller)?;
+		let price: BalanceOf<T> = T::StakeForEachKitty::get();
+	}: _(RawOrigin::Signed(caller), kitty_id, asset_id, price)
+	verify {
+		assert_eq!(ListForSale::<T>::get(kitty_id), Some(price));
+		assert_eq!(ListingAsset::<T>::get(kitty_id), Some(asset_id));
+	}
+
+	sell_with_expiry {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, T::MaxKittiesOwned::get())?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		// 新的失效区块的队列也只剩一个空位
+		let expires = frame_system::Pallet::<T>::block_number() + 20u32.into();
+		ListingsExpiringAt::<T>::insert(expires, almost_full_queue::<T, T::MaxListingsPerBlock>()?);
+		let price: BalanceOf<T> = T::StakeForEachKitty::get();
+	}: _(RawOrigin::Signed(caller), kitty_id, price, expires)
+	verify {
+		assert_eq!(ListForSale::<T>::get(kitty_id), Some(price));
+		assert_eq!(ListingExpiry::<T>::get(kitty_id), Some(expires));
+	}
+
+	sell_batch {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let ids = create_kitties::<T>(&caller, T::MaxKittiesOwned::get())?;
+		let price: BalanceOf<T> = T::StakeForEachKitty::get();
+		let mut listings = Vec::new();
+		for kitty_id in ids.into_iter().take(n as usize) {
+			list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+			listings.push((kitty_id, Some(price)));
+		}
+		let kitty_id = listings[0].0;
+		let listings = BoundedVec::try_from(listings).map_err(|_| "too many listings")?;
+	}: _(RawOrigin::Signed(caller), listings)
+	verify {
+		assert_eq!(ListForSale::<T>::get(kitty_id), Some(price));
+		assert_eq!(ListingExpiry::<T>::get(kitty_id), None);
+	}
+
+	transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, T::MaxKittiesOwned::get())?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		add_approval_and_rentals::<T>(&caller, kitty_id)?;
+		let recipient = funded_account::<T>("recipient");
+		create_kitties::<T>(&recipient, T::MaxKittiesOwned::get() - 1)?;
+	}: _(RawOrigin::Signed(caller), recipient.clone(), kitty_id)
+	verify {
+		assert_eq!(Owner::<T>::get(kitty_id), Some(recipient));
+		assert_eq!(ListForSale::<T>::get(kitty_id), None);
+	}
+
+	transfer_batch {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let ids = create_kitties::<T>(&caller, T::MaxKittiesOwned::get())?;
+		let mut kitty_ids = Vec::new();
+		for kitty_id in ids.into_iter().take(n as usize) {
+			list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+			add_approval_and_rentals::<T>(&caller, kitty_id)?;
+			kitty_ids.push(kitty_id);
+		}
+		let recipient = funded_account::<T>("recipient");
+		create_kitties::<T>(&recipient, T::MaxKittiesOwned::get() - n)?;
+		let kitty_ids = BoundedVec::try_from(kitty_ids).map_err(|_| "too many kitties")?;
+	}: _(RawOrigin::Signed(caller), recipient.clone(), kitty_ids)
+	verify {
+		assert_eq!(OwnedKitties::<T>::get(&recipient).len() as u32, T::MaxKittiesOwned::get());
+	}
+
+	transfer_from {
+		// 调用者是所有者的操作者，没有单独的授权
+		let caller: T::AccountId = whitelisted_caller();
+		let owner = funded_account::<T>("owner");
+		let kitty_id = create_kitties::<T>(&owner, T::MaxKittiesOwned::get())?[0];
+		list_with_full_expiry_queue::<T>(&owner, kitty_id)?;
+		add_approval_and_rentals::<T>(&owner, kitty_id)?;
+		SubstrateKitties::<T>::set_approval_for_all(RawOrigin::Signed(owner.clone()).into(), caller.clone(), true)?;
+		let recipient = funded_account::<T>("recipient");
+		create_kitties::<T>(&recipient, T::MaxKittiesOwned::get() - 1)?;
+	}: _(RawOrigin::Signed(caller), owner, recipient.clone(), kitty_id)
+	verify {
+		assert_eq!(Owner::<T>::get(kitty_id), Some(recipient));
+	}
+
+	buy {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 1)?;
+		// 卖家不是创建者，购买时需要支付版税
+		let creator = funded_account::<T>("creator");
+		let kitty_id = create_kitties::<T>(&creator, 1)?[0];
+		let seller = funded_account::<T>("seller");
+		create_kitties::<T>(&seller, T::MaxKittiesOwned::get() - 1)?;
+		SubstrateKitties::<T>::transfer(RawOrigin::Signed(creator).into(), seller.clone(), kitty_id)?;
+		list_with_full_expiry_queue::<T>(&seller, kitty_id)?;
+		add_approval_and_rentals::<T>(&seller, kitty_id)?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+	}
+
+	buy_at_most {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 1)?;
+		let creator = funded_account::<T>("creator");
+		let kitty_id = create_kitties::<T>(&creator, 1)?[0];
+		let seller = funded_account::<T>("seller");
+		create_kitties::<T>(&seller, T::MaxKittiesOwned::get() - 1)?;
+		SubstrateKitties::<T>::transfer(RawOrigin::Signed(creator).into(), seller.clone(), kitty_id)?;
+		list_with_full_expiry_queue::<T>(&seller, kitty_id)?;
+		add_approval_and_rentals::<T>(&seller, kitty_id)?;
+		let max_price: BalanceOf<T> = T::StakeForEachKitty::get();
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, max_price, None)
+	verify {
+		assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+	}
+
+	// 以资产计价的挂单，价款分别转给手续费账户、创建者和卖家
+	buy_with_asset {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 1)?;
+		let creator = funded_account::<T>("creator");
+		let kitty_id = create_kitties::<T>(&creator, 1)?[0];
+		let seller = funded_account::<T>("seller");
+		create_kitties::<T>(&seller, T::MaxKittiesOwned::get() - 1)?;
+		SubstrateKitties::<T>::transfer(RawOrigin::Signed(creator).into(), seller.clone(), kitty_id)?;
+		let asset_id = create_asset::<T>(&seller, &caller)?;
+		SubstrateKitties::<T>::sell_for_asset(
+			RawOrigin::Signed(seller.clone()).into(),
+			kitty_id,
+			asset_id,
+			T::StakeForEachKitty::get(),
+		)?;
+		add_approval_and_rentals::<T>(&seller, kitty_id)?;
+	}: buy(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+		assert_eq!(ListingAsset::<T>::get(kitty_id), None);
+	}
+
+	offer_sire {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		let price: BalanceOf<T> = T::StakeForEachKitty::get();
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(price))
+	verify {
+		assert_eq!(SireListings::<T>::get(kitty_id), Some(price));
+	}
+
+	commit_create {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		fill_reveal_queue::<T>(1)?;
+		let commitment_id = NextCommitmentId::<T>::get();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Commitments::<T>::get(commitment_id).map(|commitment| commitment.owner), Some(caller));
+	}
+
+	commit_create_batch {
+		let n in 1 .. T::MaxBatchSize::get().min(T::MaxCommitmentsPerBlock::get());
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		fill_reveal_queue::<T>(n)?;
+		let commitment_id = NextCommitmentId::<T>::get() + n as u64 - 1;
+	}: _(RawOrigin::Signed(caller.clone()), n)
+	verify {
+		assert_eq!(Commitments::<T>::get(commitment_id).map(|commitment| commitment.owner), Some(caller));
+	}
+
+	commit_breed {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let ids = create_kitties::<T>(&caller, 2)?;
+		let (kitty_id_1, kitty_id_2) = (ids[0], ids[1]);
+		set_gender::<T>(kitty_id_1, Gender::Male);
+		set_gender::<T>(kitty_id_2, Gender::Female);
+		fill_reveal_queue::<T>(1)?;
+		let commitment_id = NextCommitmentId::<T>::get();
+	}: _(RawOrigin::Signed(caller), kitty_id_1, kitty_id_2)
+	verify {
+		assert_eq!(
+			Commitments::<T>::get(commitment_id).and_then(|commitment| commitment.parents),
+			Some((kitty_id_1, kitty_id_2))
+		);
+	}
+
+	commit_breed_with_sire {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		set_gender::<T>(kitty_id, Gender::Female);
+		let sire_id = offer_sire_of::<T>(&funded_account::<T>("sire_owner"))?;
+		fill_reveal_queue::<T>(1)?;
+		let commitment_id = NextCommitmentId::<T>::get();
+	}: _(RawOrigin::Signed(caller), kitty_id, sire_id)
+	verify {
+		assert_eq!(
+			Commitments::<T>::get(commitment_id).and_then(|commitment| commitment.parents),
+			Some((kitty_id, sire_id))
+		);
+	}
+
+	reveal {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let ids = create_kitties::<T>(&caller, 2)?;
+		let (kitty_id_1, kitty_id_2) = (ids[0], ids[1]);
+		set_gender::<T>(kitty_id_1, Gender::Male);
+		set_gender::<T>(kitty_id_2, Gender::Female);
+		fill_reveal_queue::<T>(1)?;
+		let commitment_id = NextCommitmentId::<T>::get();
+		SubstrateKitties::<T>::commit_breed(RawOrigin::Signed(caller.clone()).into(), kitty_id_1, kitty_id_2)?;
+		// 跳过自动揭示，在揭示区块之后手动揭示
+		let reveal_at = frame_system::Pallet::<T>::block_number() + T::RevealDelay::get();
+		frame_system::Pallet::<T>::set_block_number(reveal_at + 1u32.into());
+		let kitty_id = SubstrateKitties::<T>::kitty_cnt().unwrap_or_else(|| 0u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), commitment_id)
+	verify {
+		assert_eq!(Commitments::<T>::get(commitment_id), None);
+		assert_eq!(SubstrateKitties::<T>::owner(kitty_id), Some(caller));
+	}
+
+	cancel_listing {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert_eq!(ListForSale::<T>::get(kitty_id), None);
+		assert_eq!(ListingExpiry::<T>::get(kitty_id), None);
+	}
+
+	// 报价数量越多，需要释放的报价越多
+	burn {
+		let o in 0 .. T::MaxOffersPerKitty::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, T::MaxKittiesOwned::get())?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		add_approval_and_rentals::<T>(&caller, kitty_id)?;
+		make_offers::<T>(kitty_id, o, frame_system::Pallet::<T>::block_number() + 10u32.into())?;
+		set_cooldown::<T>(kitty_id);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert_eq!(Owner::<T>::get(kitty_id), None);
+		assert_eq!(OwnedKitties::<T>::get(&caller).len() as u32, T::MaxKittiesOwned::get() - 1);
+	}
+
+	sell_dutch {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		let start_price: BalanceOf<T> = T::StakeForEachKitty::get();
+		let end_price: BalanceOf<T> = 0u32.into();
+		let duration: T::BlockNumber = 10u32.into();
+	}: _(RawOrigin::Signed(caller), kitty_id, start_price, end_price, duration)
+	verify {
+		assert!(DutchListings::<T>::contains_key(kitty_id));
+		assert_eq!(ListingExpiry::<T>::get(kitty_id), None);
+	}
+
+	gift {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		let expires = frame_system::Pallet::<T>::block_number() + T::GiftExpiry::get();
+		GiftsExpiringAt::<T>::insert(expires, almost_full_queue::<T, T::MaxGiftsPerBlock>()?);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+	}: _(RawOrigin::Signed(caller), recipient.clone(), kitty_id)
+	verify {
+		assert_eq!(PendingGifts::<T>::get(kitty_id).map(|gift| gift.to), Some(recipient));
+	}
+
+	accept_gift {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, T::MaxKittiesOwned::get())?[0];
+		add_approval_and_rentals::<T>(&caller, kitty_id)?;
+		let recipient = funded_account::<T>("recipient");
+		create_kitties::<T>(&recipient, T::MaxKittiesOwned::get() - 1)?;
+		SubstrateKitties::<T>::gift(RawOrigin::Signed(caller).into(), recipient.clone(), kitty_id)?;
+	}: _(RawOrigin::Signed(recipient.clone()), kitty_id)
+	verify {
+		assert_eq!(Owner::<T>::get(kitty_id), Some(recipient));
+	}
+
+	reject_gift {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		SubstrateKitties::<T>::gift(RawOrigin::Signed(caller).into(), recipient.clone(), kitty_id)?;
+	}: _(RawOrigin::Signed(recipient), kitty_id)
+	verify {
+		assert!(!PendingGifts::<T>::contains_key(kitty_id));
+	}
+
+	list_for_rent {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		let price_per_block: BalanceOf<T> = 1u32.into();
+		let max_duration: T::BlockNumber = 10u32.into();
+	}: _(RawOrigin::Signed(caller), kitty_id, price_per_block, max_duration)
+	verify {
+		assert!(RentalListings::<T>::contains_key(kitty_id));
+	}
+
+	cancel_rent_listing {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		add_approval_and_rentals::<T>(&caller, kitty_id)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(!RentalListings::<T>::contains_key(kitty_id));
+	}
+
+	rent {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		add_approval_and_rentals::<T>(&caller, kitty_id)?;
+		let duration: T::BlockNumber = 10u32.into();
+		let end = frame_system::Pallet::<T>::block_number() + duration;
+		LeasesExpiringAt::<T>::insert(end, almost_full_queue::<T, T::MaxLeasesPerBlock>()?);
+		let renter = funded_account::<T>("renter");
+	}: _(RawOrigin::Signed(renter.clone()), kitty_id, duration)
+	verify {
+		assert_eq!(Leases::<T>::get(kitty_id).map(|lease| lease.renter), Some(renter));
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		let approved: T::AccountId = account("approved", 0, SEED);
+	}: _(RawOrigin::Signed(caller), approved.clone(), kitty_id)
+	verify {
+		assert_eq!(Approvals::<T>::get(kitty_id), Some(approved));
+	}
+
+	clear_approval {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		add_approval_and_rentals::<T>(&caller, kitty_id)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert_eq!(Approvals::<T>::get(kitty_id), None);
+	}
+
+	set_approval_for_all {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert!(Operators::<T>::contains_key(&caller, &operator));
+	}
+
+	// 已有的报价全部过期，报价时需要逐个清理
+	make_offer {
+		let o in 0 .. T::MaxOffersPerKitty::get();
+		let owner = funded_account::<T>("owner");
+		let kitty_id = create_kitties::<T>(&owner, 1)?[0];
+		let now = frame_system::Pallet::<T>::block_number();
+		make_offers::<T>(kitty_id, o, now + 1u32.into())?;
+		frame_system::Pallet::<T>::set_block_number(now + 1u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let amount: BalanceOf<T> = T::StakeForEachKitty::get();
+		let expires = now + 10u32.into();
+		// 新报价所在的失效队列只剩一个空位
+		let lapsing: Vec<(T::KittyIndex, T::AccountId)> = almost_full_queue::<T, T::MaxOffersPerBlock>()?
+			.into_iter()
+			.enumerate()
+			.map(|(i, id)| (id, account("lapsing", i as u32, SEED)))
+			.collect();
+		OffersExpiringAt::<T>::insert(expires, BoundedVec::try_from(lapsing).map_err(|_| "queue too long")?);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, amount, expires)
+	verify {
+		let offers = Offers::<T>::get(kitty_id);
+		assert_eq!(offers.len(), 1);
+		assert_eq!(offers[0].buyer, caller);
+	}
+
+	withdraw_offer {
+		let owner = funded_account::<T>("owner");
+		let kitty_id = create_kitties::<T>(&owner, 1)?[0];
+		let expires = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get() - 1, expires)?;
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let amount: BalanceOf<T> = T::StakeForEachKitty::get();
+		SubstrateKitties::<T>::make_offer(RawOrigin::Signed(caller.clone()).into(), kitty_id, amount, expires)?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert!(Offers::<T>::get(kitty_id).iter().all(|offer| offer.buyer != caller));
+	}
+
+	// 报价数量越多，需要释放的其他报价越多
+	accept_offer {
+		let o in 1 .. T::MaxOffersPerKitty::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 1)?;
+		// 卖家不是创建者，接受报价时需要支付版税
+		let creator = funded_account::<T>("creator");
+		let kitty_id = create_kitties::<T>(&creator, 1)?[0];
+		SubstrateKitties::<T>::transfer(RawOrigin::Signed(creator).into(), caller.clone(), kitty_id)?;
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		add_approval_and_rentals::<T>(&caller, kitty_id)?;
+		let expires = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		let buyer = make_offers::<T>(kitty_id, o, expires)?.pop().ok_or("no offers")?;
+		create_kitties::<T>(&buyer, T::MaxKittiesOwned::get() - 1)?;
+	}: _(RawOrigin::Signed(caller), kitty_id, buyer.clone())
+	verify {
+		assert_eq!(Owner::<T>::get(kitty_id), Some(buyer));
+		assert!(Offers::<T>::get(kitty_id).is_empty());
+	}
+
+	create_auction {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		list_with_full_expiry_queue::<T>(&caller, kitty_id)?;
+		let end = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		AuctionsEndingAt::<T>::insert(end, almost_full_queue::<T, T::MaxAuctionsPerBlock>()?);
+		let reserve_price: BalanceOf<T> = T::StakeForEachKitty::get();
+	}: _(RawOrigin::Signed(caller), kitty_id, reserve_price, end)
+	verify {
+		assert!(Auctions::<T>::contains_key(kitty_id));
+		assert_eq!(ListForSale::<T>::get(kitty_id), None);
+	}
+
+	// 已有最高出价，出价时释放上一个出价者锁定的金额
+	bid {
+		let seller = funded_account::<T>("seller");
+		let kitty_id = create_kitties::<T>(&seller, 1)?[0];
+		let reserve_price: BalanceOf<T> = T::StakeForEachKitty::get();
+		let end = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		SubstrateKitties::<T>::create_auction(RawOrigin::Signed(seller).into(), kitty_id, reserve_price, end)?;
+		let bidder = funded_account::<T>("bidder");
+		SubstrateKitties::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, reserve_price)?;
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let amount = reserve_price + reserve_price;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, amount)
+	verify {
+		assert_eq!(Auctions::<T>::get(kitty_id).and_then(|auction| auction.best_bid), Some((caller, amount)));
+	}
+
+	cancel_auction {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = create_kitties::<T>(&caller, 1)?[0];
+		let end = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		AuctionsEndingAt::<T>::insert(end, almost_full_queue::<T, T::MaxAuctionsPerBlock>()?);
+		let reserve_price: BalanceOf<T> = T::StakeForEachKitty::get();
+		SubstrateKitties::<T>::create_auction(RawOrigin::Signed(caller.clone()).into(), kitty_id, reserve_price, end)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(!Auctions::<T>::contains_key(kitty_id));
+	}
+
+	impl_benchmark_test_suite!(SubstrateKitties, crate::mock::new_test_ext(), crate::mock::Test);
+}
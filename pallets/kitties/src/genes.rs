@@ -0,0 +1,186 @@
+//! Trait genes decoded from kitty DNA.
+//!
+//! Each DNA byte is one gene: the low nibble is the dominant allele that decides how the kitty
+//! looks, the high nibble is the recessive allele that is only passed on to offspring. Alleles
+//! 0-7 are the base forms of a trait and 8-15 are mutated forms of the same trait.
+
+use crate::Gender;
+use codec::{Decode, Encode};
+use frame_support::{sp_runtime::Permill, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_io::hashing::blake2_128;
+
+// 各个基因在DNA中的位置，第0个字节决定性别，其余未使用的字节留给以后新增的基因
+pub const BODY_COLOUR: usize = 1;
+pub const PATTERN: usize = 2;
+pub const EYE_SHAPE: usize = 3;
+pub const EYE_COLOUR: usize = 4;
+pub const MOUTH: usize = 5;
+pub const RARITY: usize = 6;
+
+// 所有决定性状的基因
+pub const TRAIT_GENES: [usize; 6] = [BODY_COLOUR, PATTERN, EYE_SHAPE, EYE_COLOUR, MOUTH, RARITY];
+
+// 普通性状的等位基因数量，更大的等位基因是变异形态
+pub const BASE_ALLELES: u8 = 8;
+
+// 显性等位基因
+pub fn dominant(dna: &[u8; 16], gene: usize) -> u8 {
+	dna[gene] & 0x0f
+}
+
+// 隐性等位基因
+pub fn recessive(dna: &[u8; 16], gene: usize) -> u8 {
+	dna[gene] >> 4
+}
+
+macro_rules! gene_trait {
+	($name:ident { $($variant:ident),* $(,)? }) => {
+		#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+		#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+		pub enum $name {
+			$($variant),*
+		}
+
+		impl $name {
+			const ALL: [$name; BASE_ALLELES as usize] = [$($name::$variant),*];
+
+			pub fn from_allele(allele: u8) -> Self {
+				Self::ALL[(allele % BASE_ALLELES) as usize]
+			}
+		}
+	};
+}
+
+gene_trait!(BodyColour {
+	Shadowgrey,
+	Salmon,
+	Cottoncandy,
+	Mauveover,
+	Aquamarine,
+	Nachocheez,
+	Harbourfog,
+	Cinderella,
+});
+
+gene_trait!(Pattern { Totesbasic, Spock, Tigerpatch, Calicool, Luckystripe, Jaguar, Leopard, Thunderstruck });
+
+gene_trait!(EyeShape { Thicccbrowz, Wonky, Serpent, Googly, Otaku, Simple, Crazy, Sass });
+
+gene_trait!(EyeColour { Gold, Bubblegum, Limegreen, Chestnut, Topaz, Mintgreen, Strawberry, Sizzurp });
+
+gene_trait!(Mouth { Whixtensions, Dali, Saycheese, Beard, Tongue, Happygokitty, Soserious, Pouty });
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RarityTier {
+	Common,
+	Uncommon,
+	Rare,
+	Epic,
+	Legendary,
+}
+
+impl RarityTier {
+	pub fn from_allele(allele: u8) -> Self {
+		match allele & 0x0f {
+			0..=7 => RarityTier::Common,
+			8..=11 => RarityTier::Uncommon,
+			12..=13 => RarityTier::Rare,
+			14 => RarityTier::Epic,
+			_ => RarityTier::Legendary,
+		}
+	}
+}
+
+// 一个性状的外观，mutated表示是变异形态
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Trait<V> {
+	pub value: V,
+	pub mutated: bool,
+}
+
+impl<V> Trait<V> {
+	fn decode(dna: &[u8; 16], gene: usize, from_allele: fn(u8) -> V) -> Self {
+		let allele = dominant(dna, gene);
+		Trait { value: from_allele(allele), mutated: allele >= BASE_ALLELES }
+	}
+}
+
+// 由DNA解析出的Kitty性状
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct KittyTraits {
+	pub gender: Gender,
+	pub body_colour: Trait<BodyColour>,
+	pub pattern: Trait<Pattern>,
+	pub eye_shape: Trait<EyeShape>,
+	pub eye_colour: Trait<EyeColour>,
+	pub mouth: Trait<Mouth>,
+	pub rarity: RarityTier,
+}
+
+impl KittyTraits {
+	pub fn from_dna(dna: &[u8; 16]) -> Self {
+		KittyTraits {
+			gender: Gender::from_dna(dna),
+			body_colour: Trait::decode(dna, BODY_COLOUR, BodyColour::from_allele),
+			pattern: Trait::decode(dna, PATTERN, Pattern::from_allele),
+			eye_shape: Trait::decode(dna, EYE_SHAPE, EyeShape::from_allele),
+			eye_colour: Trait::decode(dna, EYE_COLOUR, EyeColour::from_allele),
+			mouth: Trait::decode(dna, MOUTH, Mouth::from_allele),
+			rarity: RarityTier::from_allele(dominant(dna, RARITY)),
+		}
+	}
+}
+
+// 普通性状的DNA，初代Kitty的所有性状都是普通形态，变异形态只能通过繁殖获得
+pub fn base_dna(mut dna: [u8; 16]) -> [u8; 16] {
+	for gene in TRAIT_GENES {
+		dna[gene] &= 0x77;
+	}
+	dna
+}
+
+// 由父母的DNA生成子代的DNA，相同的随机种子总是得到相同的结果。
+//
+// 父母各自以3/4的概率遗传显性等位基因，否则遗传隐性等位基因。两个等位基因中较小的是显性的，
+// 因此变异形态通常是隐性的。当遗传的两个等位基因是一对(2k, 2k+1)时，子代以mutation_chance的
+// 概率变异为等位基因8+k，稀有的性状只能由特定的父母组合得到。
+pub fn mix(dna_1: &[u8; 16], dna_2: &[u8; 16], seed: &[u8; 16], mutation_chance: Permill) -> [u8; 16] {
+	let mut dna = [0u8; 16];
+	for i in 0..dna.len() {
+		let random = (seed, i as u8).using_encoded(blake2_128);
+		dna[i] = if TRAIT_GENES.contains(&i) {
+			let a = inherit(dna_1, i, random[0]);
+			let b = inherit(dna_2, i, random[1]);
+			let (dominant, recessive) = (a.min(b), a.max(b));
+			let roll = u32::from_le_bytes([random[2], random[3], random[4], random[5]]) % 1_000_000;
+			if dominant / 2 == recessive / 2 && dominant != recessive && roll < mutation_chance.deconstruct() {
+				(recessive << 4) | (BASE_ALLELES + dominant / 2)
+			} else {
+				(recessive << 4) | dominant
+			}
+		} else if i == 0 {
+			// 子代的性别随机
+			random[0]
+		} else {
+			// 未使用的基因随机选择父母中的一方
+			(random[0] & dna_1[i]) | (!random[0] & dna_2[i])
+		};
+	}
+	dna
+}
+
+// 父母遗传给子代的等位基因
+fn inherit(dna: &[u8; 16], gene: usize, random: u8) -> u8 {
+	if random % 4 == 0 {
+		recessive(dna, gene)
+	} else {
+		dominant(dna, gene)
+	}
+}
@@ -8,25 +8,246 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod genes;
+pub mod migrations;
+#[cfg(feature = "std")]
+pub mod serde_balance;
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
-        dispatch::DispatchResult,
+        dispatch::{DispatchError, DispatchResult},
         ensure,
         pallet_prelude::*,
-        sp_runtime::traits::{AtLeast32BitUnsigned, Bounded},
-        traits::{Currency, ExistenceRequirement, Randomness, ReservableCurrency},
+        sp_runtime::{
+            traits::{AtLeast32BitUnsigned, Bounded, SaturatedConversion, Saturating, Zero},
+            Perbill, Permill,
+        },
+        traits::{
+            fungibles, Currency, ExistenceRequirement, NamedReservableCurrency, OnUnbalanced, Randomness,
+            WithdrawReasons,
+        },
+        sp_std::vec::Vec,
+        transactional, BoundedVec,
     };
+    use crate::genes::{self, KittyTraits};
+    use crate::WeightInfo;
     use frame_system::{ensure_signed, pallet_prelude::*};
     use scale_info::TypeInfo;
+    #[cfg(feature = "std")]
+    use serde::{Deserialize, Serialize};
     use sp_io::hashing::blake2_128;
 
-    #[derive(Encode, Decode, TypeInfo)]
-    pub struct Kitty {
+    // 当前的存储版本
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
+    // 查询出售列表时每页的Kitty数量
+    pub const LISTINGS_PAGE_SIZE: u32 = 20;
+
+    // Kitty质押使用的锁定标识
+    pub const KITTY_RESERVE_ID: [u8; 8] = *b"py/kitty";
+    // 拍卖出价和报价使用的锁定标识
+    pub const BID_RESERVE_ID: [u8; 8] = *b"py/kbids";
+
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub enum Gender {
+        Male,
+        Female,
+    }
+
+    impl Gender {
+        // 由DNA决定性别
+        pub fn from_dna(dna: &[u8; 16]) -> Self {
+            if dna[0] % 2 == 0 {
+                Gender::Male
+            } else {
+                Gender::Female
+            }
+        }
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Kitty<KittyIndex, BlockNumber> {
+        pub dna: [u8; 16],
+        // 父母的ID，初代Kitty没有父母
+        pub parents: Option<(KittyIndex, KittyIndex)>,
+        // 代数，初代Kitty为0
+        pub generation: u32,
+        // 出生时的区块高度
+        pub birth_block: BlockNumber,
+        pub gender: Gender,
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+    pub struct BreedingState<BlockNumber> {
+        // 已繁殖的次数
+        pub breed_count: u32,
+        // 冷却结束、可再次繁殖的区块
+        pub ready_at: BlockNumber,
+    }
+
+    // 冷却时间最多翻倍的次数
+    const MAX_COOLDOWN_DOUBLINGS: u32 = 7;
+
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Auction<AccountId, Balance, BlockNumber> {
+        pub seller: AccountId,
+        // 保留价，出价不能低于保留价
+        pub reserve_price: Balance,
+        // 拍卖结束的区块
+        pub end: BlockNumber,
+        // 当前的最高出价
+        pub best_bid: Option<(AccountId, Balance)>,
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct DutchListing<Balance, BlockNumber> {
+        // 起始价格
+        pub start_price: Balance,
+        // 最终价格
+        pub end_price: Balance,
+        // 开始降价的区块
+        pub start: BlockNumber,
+        // 从起始价格降到最终价格经过的区块数
+        pub duration: BlockNumber,
+    }
+
+    impl<Balance, BlockNumber> DutchListing<Balance, BlockNumber>
+    where
+        Balance: AtLeast32BitUnsigned + Copy,
+        BlockNumber: AtLeast32BitUnsigned + Copy,
+    {
+        // 按区块高度线性降价，到期后保持最终价格
+        pub fn price_at(&self, now: BlockNumber) -> Balance {
+            let elapsed = now.saturating_sub(self.start).min(self.duration);
+            let discount = Perbill::from_rational(elapsed, self.duration) * (self.start_price - self.end_price);
+            self.start_price - discount
+        }
+    }
+
+    pub type DutchListingOf<T> = DutchListing<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Offer<AccountId, Balance, BlockNumber> {
+        pub buyer: AccountId,
+        // 报价金额，报价期间锁定在买家账户
+        pub amount: Balance,
+        // 报价失效的区块
+        pub expires: BlockNumber,
+    }
+
+    pub type OfferOf<T> =
+        Offer<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Gift<AccountId, BlockNumber> {
+        pub from: AccountId,
+        pub to: AccountId,
+        // 赠送失效的区块
+        pub expires: BlockNumber,
+    }
+
+    pub type GiftOf<T> = Gift<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct RentalListing<Balance, BlockNumber> {
+        // 每个区块的租金
+        pub price_per_block: Balance,
+        // 最长租期（区块数）
+        pub max_duration: BlockNumber,
+    }
+
+    pub type RentalListingOf<T> = RentalListing<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Lease<AccountId, BlockNumber> {
+        // 租用者，租期内拥有Kitty的使用权
+        pub renter: AccountId,
+        // 租期结束、Kitty归还所有者的区块
+        pub end: BlockNumber,
+    }
+
+    pub type LeaseOf<T> = Lease<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Commitment<AccountId, KittyIndex, Balance, BlockNumber> {
+        pub owner: AccountId,
+        // 繁殖的父母，None表示创建初代Kitty
+        pub parents: Option<(KittyIndex, KittyIndex)>,
+        // 第二个父母是付费使用的种猫，揭示时不要求承诺者拥有其使用权
+        pub hired_sire: bool,
+        // 使用该区块的哈希生成DNA，在下一个区块自动揭示，之后也可以手动揭示
+        pub reveal_at: BlockNumber,
+        // 承诺失效、退还质押的区块
+        pub expires: BlockNumber,
+        // 承诺时锁定的质押
+        pub deposit: Balance,
+    }
+
+    pub type CommitmentOf<T> = Commitment<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::KittyIndex,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    pub type AuctionOf<T> =
+        Auction<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+    pub type KittyOf<T> = Kitty<<T as Config>::KittyIndex, <T as frame_system::Config>::BlockNumber>;
+
+    // 查询接口返回的Kitty信息，包括解析后的性状和出售状态
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId> {
+        pub id: KittyIndex,
+        pub owner: AccountId,
+        // 租期内拥有使用权的租用者，未出租时为None
+        pub user: Option<AccountId>,
+        // 租期结束的区块
+        pub lease_end: Option<BlockNumber>,
         pub dna: [u8; 16],
+        pub parents: Option<(KittyIndex, KittyIndex)>,
+        pub generation: u32,
+        pub birth_block: BlockNumber,
+        pub traits: KittyTraits,
+        // 当前的出售价格，未出售时为None，JSON中以字符串表示
+        #[cfg_attr(
+            feature = "std",
+            serde(
+                with = "crate::serde_balance::option",
+                bound(serialize = "Balance: std::fmt::Display", deserialize = "Balance: std::str::FromStr")
+            )
+        )]
+        pub price: Option<Balance>,
+        // 以资产计价出售时使用的资产
+        pub price_asset: Option<AssetId>,
+        // 出售挂单失效的区块
+        pub listing_expires: Option<BlockNumber>,
+        // 繁殖冷却结束的区块
+        pub ready_at: BlockNumber,
     }
 
+    pub type KittyInfoOf<T> = KittyInfo<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::KittyIndex,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+        AssetIdOf<T>,
+    >;
+
     type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    type NegativeImbalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+    pub type AssetIdOf<T> =
+        <<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
 
     #[pallet::storage]
     #[pallet::getter(fn kitty_cnt)]
@@ -34,7 +255,7 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn kitties)]
-    pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, Option<Kitty>, ValueQuery>;
+    pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, Option<KittyOf<T>>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn owner)]
@@ -44,18 +265,216 @@ pub mod pallet {
     #[pallet::getter(fn kitties_list_for_sales)]
     pub type ListForSale<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, Option<BalanceOf<T>>, ValueQuery>;
 
+    // 以资产计价出售的Kitty使用的资产，不存在时以原生代币计价
+    #[pallet::storage]
+    #[pallet::getter(fn listing_asset)]
+    pub type ListingAsset<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, AssetIdOf<T>, OptionQuery>;
+
+    // 出售挂单失效的区块
+    #[pallet::storage]
+    #[pallet::getter(fn listing_expiry)]
+    pub type ListingExpiry<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, T::BlockNumber, OptionQuery>;
+
+    // 挂单失效的区块 -> 在该区块失效的挂单
+    #[pallet::storage]
+    pub type ListingsExpiringAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<T::KittyIndex, T::MaxListingsPerBlock>, ValueQuery>;
+
+    // 以荷兰式拍卖出售的Kitty
+    #[pallet::storage]
+    #[pallet::getter(fn dutch_listings)]
+    pub type DutchListings<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, DutchListingOf<T>, OptionQuery>;
+
+    // Kitty收到的报价
+    #[pallet::storage]
+    #[pallet::getter(fn offers)]
+    pub type Offers<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, BoundedVec<OfferOf<T>, T::MaxOffersPerKitty>, ValueQuery>;
+
+    // 报价失效的区块 -> 在该区块失效的报价 (kitty_id, 买家)
+    #[pallet::storage]
+    pub type OffersExpiringAt<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        BoundedVec<(T::KittyIndex, T::AccountId), T::MaxOffersPerBlock>,
+        ValueQuery,
+    >;
+
+    // 作为种猫出租的Kitty及其配种费
+    #[pallet::storage]
+    #[pallet::getter(fn sire_listings)]
+    pub type SireListings<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, Option<BalanceOf<T>>, ValueQuery>;
+
+    // 正在拍卖的Kitty
+    #[pallet::storage]
+    #[pallet::getter(fn auctions)]
+    pub type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, AuctionOf<T>, OptionQuery>;
+
+    // 拍卖结束的区块 -> 在该区块结算的拍卖
+    #[pallet::storage]
+    pub type AuctionsEndingAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<T::KittyIndex, T::MaxAuctionsPerBlock>, ValueQuery>;
+
+    // 等待接收方确认的赠送
+    #[pallet::storage]
+    #[pallet::getter(fn pending_gifts)]
+    pub type PendingGifts<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, GiftOf<T>, OptionQuery>;
+
+    // 赠送失效的区块 -> 在该区块失效的赠送
+    #[pallet::storage]
+    pub type GiftsExpiringAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<T::KittyIndex, T::MaxGiftsPerBlock>, ValueQuery>;
+
+    // 可以租用的Kitty及其租金
+    #[pallet::storage]
+    #[pallet::getter(fn rental_listings)]
+    pub type RentalListings<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, RentalListingOf<T>, OptionQuery>;
+
+    // 正在出租的Kitty
+    #[pallet::storage]
+    #[pallet::getter(fn leases)]
+    pub type Leases<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, LeaseOf<T>, OptionQuery>;
+
+    // 租期结束的区块 -> 在该区块归还的Kitty
+    #[pallet::storage]
+    pub type LeasesExpiringAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<T::KittyIndex, T::MaxLeasesPerBlock>, ValueQuery>;
+
+    // 每个Kitty实际质押的金额
+    #[pallet::storage]
+    #[pallet::getter(fn kitty_deposits)]
+    pub type KittyDeposits<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, BalanceOf<T>, ValueQuery>;
+
+    // Kitty的创建者，转售时向创建者支付版税
+    #[pallet::storage]
+    #[pallet::getter(fn creators)]
+    pub type Creators<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, T::AccountId, OptionQuery>;
+
+    // 被授权转移某个Kitty的账户
+    #[pallet::storage]
+    #[pallet::getter(fn approvals)]
+    pub type Approvals<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, T::AccountId, OptionQuery>;
+
+    // (所有者, 操作者) -> 操作者可以转移所有者的全部Kitty
+    #[pallet::storage]
+    pub type Operators<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    // 账户 -> 该账户拥有的Kitty列表
+    #[pallet::storage]
+    #[pallet::getter(fn owned_kitties)]
+    pub type OwnedKitties<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<T::KittyIndex, T::MaxKittiesOwned>, ValueQuery>;
+
+    // Kitty的繁殖状态
+    #[pallet::storage]
+    #[pallet::getter(fn breeding_state)]
+    pub type BreedingStates<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, BreedingState<T::BlockNumber>, ValueQuery>;
+
+    // 下一个承诺的ID
+    #[pallet::storage]
+    pub type NextCommitmentId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    // 等待揭示的创建和繁殖承诺
+    #[pallet::storage]
+    #[pallet::getter(fn commitments)]
+    pub type Commitments<T: Config> = StorageMap<_, Twox64Concat, u64, CommitmentOf<T>, OptionQuery>;
+
+    // 揭示的区块 -> 在该区块自动揭示的承诺
+    #[pallet::storage]
+    pub type CommitmentsRevealingAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<u64, T::MaxCommitmentsPerBlock>, ValueQuery>;
+
+    // 承诺失效的区块 -> 在该区块失效的承诺
+    #[pallet::storage]
+    pub type CommitmentsExpiringAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<u64, T::MaxCommitmentsPerBlock>, ValueQuery>;
+
+    // (基因, 显性等位基因) -> 现存的表现该性状的Kitty数量
+    #[pallet::storage]
+    pub type TraitCounts<T: Config> = StorageDoubleMap<_, Twox64Concat, u8, Twox64Concat, u8, u32, ValueQuery>;
+
+    // 冷却结束的区块 -> 在该区块结束冷却的Kitty
+    #[pallet::storage]
+    pub type CooldownEnds<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Blake2_128Concat, T::KittyIndex, (), OptionQuery>;
+
     #[pallet::pallet]
     #[pallet::generate_store(pub (super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
         type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
-        type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+        type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
         #[pallet::constant]
         type StakeForEachKitty: Get<BalanceOf<Self>>;
         type KittyIndex: Parameter + AtLeast32BitUnsigned + Default + Copy + Bounded;
+        // 每个账户最多可拥有的Kitty数量
+        #[pallet::constant]
+        type MaxKittiesOwned: Get<u32>;
+        // 繁殖的基础冷却时间（区块数），每繁殖一次冷却时间翻倍
+        #[pallet::constant]
+        type BreedCooldown: Get<Self::BlockNumber>;
+        // 每个区块最多结算的拍卖数量
+        #[pallet::constant]
+        type MaxAuctionsPerBlock: Get<u32>;
+        // 每个Kitty最多同时存在的报价数量
+        #[pallet::constant]
+        type MaxOffersPerKitty: Get<u32>;
+        // 每笔交易收取的市场手续费比例
+        #[pallet::constant]
+        type MarketplaceFee: Get<Permill>;
+        // 每次转售支付给创建者的版税比例
+        #[pallet::constant]
+        type CreatorRoyalty: Get<Permill>;
+        // 市场手续费的去向
+        type OnMarketplaceFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
+        // 赠送等待接收方确认的区块数，超时后自动取消
+        #[pallet::constant]
+        type GiftExpiry: Get<Self::BlockNumber>;
+        // 每个区块最多失效的赠送数量
+        #[pallet::constant]
+        type MaxGiftsPerBlock: Get<u32>;
+        // 每个区块最多到期的租约数量
+        #[pallet::constant]
+        type MaxLeasesPerBlock: Get<u32>;
+        // 批量操作一次最多处理的Kitty数量
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+        // 每个区块最多失效的挂单数量
+        #[pallet::constant]
+        type MaxListingsPerBlock: Get<u32>;
+        // 可用于购买Kitty的资产，质押仍使用原生代币
+        type Assets: fungibles::Transfer<Self::AccountId, Balance = BalanceOf<Self>>;
+        // 以资产支付时市场手续费的收款账户
+        type AssetFeeCollector: Get<Self::AccountId>;
+        // 繁殖时遗传的等位基因组成一对时发生变异的概率
+        #[pallet::constant]
+        type MutationChance: Get<Permill>;
+        // 承诺后经过多少个区块生成DNA，承诺在之后的区块自动揭示
+        #[pallet::constant]
+        type RevealDelay: Get<Self::BlockNumber>;
+        // 可以揭示的区块数，超时后承诺失效并退还质押
+        // 不能超过frame_system保存的区块哈希数量(BlockHashCount)
+        #[pallet::constant]
+        type RevealWindow: Get<Self::BlockNumber>;
+        // 每个区块最多揭示和失效的承诺数量
+        #[pallet::constant]
+        type MaxCommitmentsPerBlock: Get<u32>;
+        // 是否允许不经过承诺直接创建和繁殖，直接创建使用的随机数可以被预测
+        #[pallet::constant]
+        type AllowInstantMint: Get<bool>;
+        // 每个区块最多失效的报价数量
+        #[pallet::constant]
+        type MaxOffersPerBlock: Get<u32>;
+        // 外部调用的权重
+        type WeightInfo: WeightInfo;
     }
 
     // Errors.
@@ -69,6 +488,45 @@ pub mod pallet {
         KittyNotForSell,
         NotEnoughBalanceForBuying,
         NotEnoughBalanceForStaking,
+        TooManyOwned,
+        SameGender,
+        KittyOnCooldown,
+        SireNotOffered,
+        KittyInAuction,
+        AuctionNotFound,
+        InvalidAuctionEnd,
+        TooManyAuctionsEnding,
+        AuctionEnded,
+        BidTooLow,
+        AuctionHasBids,
+        InvalidDutchAuction,
+        NotApproved,
+        InvalidOfferExpiry,
+        TooManyOffers,
+        OfferNotFound,
+        OfferExpired,
+        GiftToSelf,
+        KittyGiftPending,
+        GiftNotFound,
+        NotGiftRecipient,
+        TooManyGiftsExpiring,
+        KittyNotForRent,
+        KittyLeased,
+        InvalidLeaseDuration,
+        TooManyLeasesExpiring,
+        BatchTooLarge,
+        InvalidListingExpiry,
+        TooManyListingsExpiring,
+        PriceTooHigh,
+        InstantMintDisabled,
+        TooManyCommitmentsRevealing,
+        ListingAssetMismatch,
+        UnknownAsset,
+        AssetPriceTooLow,
+        CommitmentNotFound,
+        RevealTooEarly,
+        TooManyCommitmentsExpiring,
+        TooManyOffersExpiring,
     }
 
     #[pallet::event]
@@ -77,111 +535,872 @@ pub mod pallet {
         KittyCreate(T::AccountId, T::KittyIndex),
         KittyTransfer(T::AccountId, T::AccountId, T::KittyIndex),
         KittyListed(T::AccountId, T::KittyIndex, Option<BalanceOf<T>>),
-        KittySold(T::AccountId, T::AccountId, T::KittyIndex),
+        // [buyer, seller, kitty_id, price, fee, royalty]
+        KittySold(T::AccountId, T::AccountId, T::KittyIndex, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+        KittyReady(T::AccountId, T::KittyIndex),
+        SireOffered(T::AccountId, T::KittyIndex, Option<BalanceOf<T>>),
+        KittySired(T::AccountId, T::AccountId, T::KittyIndex, BalanceOf<T>),
+        AuctionCreated(T::AccountId, T::KittyIndex, BalanceOf<T>, T::BlockNumber),
+        BidPlaced(T::AccountId, T::KittyIndex, BalanceOf<T>),
+        AuctionSettled(T::AccountId, T::AccountId, T::KittyIndex, BalanceOf<T>),
+        AuctionCancelled(T::AccountId, T::KittyIndex),
+        KittyListedDutch(T::AccountId, T::KittyIndex, BalanceOf<T>, BalanceOf<T>, T::BlockNumber),
+        KittyBurned(T::AccountId, T::KittyIndex),
+        // [owner, approved, kitty_id]
+        Approval(T::AccountId, T::AccountId, T::KittyIndex),
+        ApprovalCleared(T::AccountId, T::KittyIndex),
+        // [owner, operator, approved]
+        ApprovalForAll(T::AccountId, T::AccountId, bool),
+        OfferMade(T::AccountId, T::KittyIndex, BalanceOf<T>, T::BlockNumber),
+        OfferWithdrawn(T::AccountId, T::KittyIndex),
+        OfferOutbid(T::AccountId, T::KittyIndex),
+        // [from, to, kitty_id, expires]
+        GiftOffered(T::AccountId, T::AccountId, T::KittyIndex, T::BlockNumber),
+        GiftAccepted(T::AccountId, T::AccountId, T::KittyIndex),
+        GiftRejected(T::AccountId, T::AccountId, T::KittyIndex),
+        GiftExpired(T::AccountId, T::AccountId, T::KittyIndex),
+        // [owner, kitty_id, price_per_block, max_duration]
+        KittyListedForRent(T::AccountId, T::KittyIndex, BalanceOf<T>, T::BlockNumber),
+        RentListingCancelled(T::AccountId, T::KittyIndex),
+        // [renter, owner, kitty_id, end, rent]
+        KittyRented(T::AccountId, T::AccountId, T::KittyIndex, T::BlockNumber, BalanceOf<T>),
+        // [renter, kitty_id]
+        LeaseEnded(T::AccountId, T::KittyIndex),
+        // [who, count]
+        BatchCompleted(T::AccountId, u32),
+        ListingCancelled(T::AccountId, T::KittyIndex),
+        ListingExpired(T::KittyIndex),
+        // [owner, kitty_id, asset_id, price]
+        KittyListedForAsset(T::AccountId, T::KittyIndex, AssetIdOf<T>, BalanceOf<T>),
+        // [owner, commitment_id, reveal_at]
+        MintCommitted(T::AccountId, u64, T::BlockNumber),
+        CommitmentRevealed(T::AccountId, u64),
+        // 承诺超时未揭示，退还锁定的质押
+        CommitmentExpired(T::AccountId, u64),
+        // [buyer, seller, kitty_id, asset_id, price, fee, royalty]
+        KittySoldForAsset(
+            T::AccountId,
+            T::AccountId,
+            T::KittyIndex,
+            AssetIdOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        // [buyer, kitty_id]
+        OfferExpired(T::AccountId, T::KittyIndex),
+    }
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        // 创世区块中的Kitty: (所有者, DNA)，所有者需要有足够的余额用于质押
+        pub kitties: Vec<(T::AccountId, [u8; 16])>,
+    }
+
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self { kitties: Vec::new() }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            for (owner, dna) in &self.kitties {
+                // 与创建Kitty相同，质押金额并更新KittyCnt
+                Pallet::<T>::create_kitty_with_stake(owner, *dna, None, 0)
+                    .unwrap_or_else(|e| panic!("failed to create genesis kitty of {:?}: {:?}", owner, e));
+            }
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            // 通知冷却结束的Kitty的所有者
+            let mut ready = 0u64;
+            for (kitty_id, _) in CooldownEnds::<T>::drain_prefix(now) {
+                ready += 1;
+                if let Some(owner) = Self::owner(kitty_id) {
+                    Self::deposit_event(Event::KittyReady(owner, kitty_id));
+                }
+            }
+            // 结算到期的拍卖
+            let ending = AuctionsEndingAt::<T>::take(now);
+            let settled = ending.len() as u64;
+            for kitty_id in ending {
+                Self::settle_auction(kitty_id);
+            }
+            // 取消到期未被接收的赠送
+            let expiring = GiftsExpiringAt::<T>::take(now);
+            let expired = expiring.len() as u64;
+            for kitty_id in expiring {
+                if let Some(gift) = PendingGifts::<T>::take(kitty_id) {
+                    Self::deposit_event(Event::GiftExpired(gift.from, gift.to, kitty_id));
+                }
+            }
+            // 归还租期结束的Kitty
+            let leases_ending = LeasesExpiringAt::<T>::take(now);
+            let returned = leases_ending.len() as u64;
+            for kitty_id in leases_ending {
+                Self::end_lease(kitty_id);
+            }
+            // 清理过期的挂单
+            let listings_ending = ListingsExpiringAt::<T>::take(now);
+            let delisted = listings_ending.len() as u64;
+            for kitty_id in listings_ending {
+                ListingExpiry::<T>::remove(kitty_id);
+                ListForSale::<T>::remove(kitty_id);
+                ListingAsset::<T>::remove(kitty_id);
+                DutchListings::<T>::remove(kitty_id);
+                Self::deposit_event(Event::ListingExpired(kitty_id));
+            }
+            // 揭示上一个区块到期的承诺，揭示失败的承诺保留到失效，期间可以手动揭示
+            let commitments_revealing = CommitmentsRevealingAt::<T>::take(now);
+            let revealed = commitments_revealing.len() as u64;
+            let mut reveal_weight: Weight = 0;
+            for commitment_id in commitments_revealing {
+                if let Some(commitment) = Self::commitments(commitment_id) {
+                    reveal_weight = reveal_weight.saturating_add(T::WeightInfo::reveal());
+                    let _ = Self::reveal_commitment(commitment_id, commitment);
+                }
+            }
+            // 释放过期的报价锁定的金额
+            let offers_ending = OffersExpiringAt::<T>::take(now);
+            let lapsed = offers_ending.len() as u64;
+            for (kitty_id, buyer) in offers_ending {
+                Offers::<T>::mutate(kitty_id, |offers| {
+                    if let Some(index) = offers.iter().position(|offer| offer.buyer == buyer) {
+                        let offer = offers.remove(index);
+                        T::Currency::unreserve_named(&BID_RESERVE_ID, &offer.buyer, offer.amount);
+                        Self::deposit_event(Event::OfferExpired(offer.buyer, kitty_id));
+                    }
+                });
+            }
+            // 退还失效的承诺锁定的质押
+            let commitments_ending = CommitmentsExpiringAt::<T>::take(now);
+            let refunded = commitments_ending.len() as u64;
+            for commitment_id in commitments_ending {
+                if let Some(commitment) = Commitments::<T>::take(commitment_id) {
+                    T::Currency::unreserve_named(&KITTY_RESERVE_ID, &commitment.owner, commitment.deposit);
+                    Self::deposit_event(Event::CommitmentExpired(commitment.owner, commitment_id));
+                }
+            }
+            T::DbWeight::get()
+                .reads_writes(
+                    8 + ready + 6 * settled + expired + returned + revealed + 2 * lapsed + 2 * refunded,
+                    7 + ready + 8 * settled + expired + 2 * returned + 4 * delisted + 2 * lapsed + 2 * refunded,
+                )
+                .saturating_add(reveal_weight)
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            crate::migrations::v1::migrate::<T>()
+                .saturating_add(crate::migrations::v2::migrate::<T>())
+                .saturating_add(crate::migrations::v3::migrate::<T>())
+        }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         // 创建
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::create())]
         pub fn create(origin: OriginFor<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            // 随机生成DNA
-            let dna = Self::random_value(&who);
+            ensure!(T::AllowInstantMint::get(), Error::<T>::InstantMintDisabled);
+            // 随机生成DNA，初代Kitty只有普通性状
+            let dna = genes::base_dna(Self::random_value(&who));
             // 创建kitty时质押一定数量的token
-            Self::create_kitty_with_stake(&who, dna)
+            Self::create_kitty_with_stake(&who, dna, None, 0)
+        }
+
+        // 批量创建，任意一个失败则全部回滚
+        #[pallet::weight(T::WeightInfo::create_batch(*count))]
+        #[transactional]
+        pub fn create_batch(origin: OriginFor<T>, count: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(T::AllowInstantMint::get(), Error::<T>::InstantMintDisabled);
+            ensure!(count <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+            let seed = Self::random_value(&who);
+            for i in 0..count {
+                // 同一交易中的随机数相同，混入序号生成不同的DNA
+                let dna = genes::base_dna((seed, i).using_encoded(blake2_128));
+                Self::create_kitty_with_stake(&who, dna, None, 0)?;
+            }
+            Self::deposit_event(Event::BatchCompleted(who, count));
+            Ok(())
         }
 
         // 繁殖
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::breed())]
         pub fn breed(origin: OriginFor<T>, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            ensure!(T::AllowInstantMint::get(), Error::<T>::InstantMintDisabled);
             // parent_id 不能相同
             ensure!(kitty_id_1 != kitty_id_2, Error::<T>::SameParentIndex);
-            // 获取Kitty1
-            let kitty1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyIndex)?;
-            // 获取Kitty2
-            let kitty2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyIndex)?;
+            // 调用者必须拥有父母的使用权
+            Self::ensure_user(&who, kitty_id_1)?;
+            Self::ensure_user(&who, kitty_id_2)?;
+            Self::do_breed(&who, kitty_id_1, kitty_id_2)
+        }
 
-            // 获取Parents Kitty的DNA
-            let dna_1 = kitty1.dna;
-            let dna_2 = kitty2.dna;
-            // 混淆DNA
-            let selector = Self::random_value(&who);
-            let mut new_dna = [0u8; 16];
-            for i in 0..dna_1.len() {
-                new_dna[i] = (selector[i] & dna_1[i]) | (!selector[i] & dna_2[i]);
+        // 出租种猫，price为None时取消出租
+        #[pallet::weight(T::WeightInfo::offer_sire())]
+        pub fn offer_sire(origin: OriginFor<T>, kitty_id: T::KittyIndex, price: Option<BalanceOf<T>>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            // 校验发起方拥有该kitty的使用权
+            Self::ensure_user(&who, kitty_id)?;
+            // 将Kitty添加到种猫列表
+            SireListings::<T>::insert(kitty_id, price);
+            Self::deposit_event(Event::SireOffered(who, kitty_id, price));
+            Ok(())
+        }
+
+        // 支付配种费，使用他人出租的种猫与自己的Kitty繁殖
+        #[pallet::weight(T::WeightInfo::breed_with_sire())]
+        #[transactional]
+        pub fn breed_with_sire(origin: OriginFor<T>, kitty_id: T::KittyIndex, sire_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(T::AllowInstantMint::get(), Error::<T>::InstantMintDisabled);
+            // parent_id 不能相同
+            ensure!(kitty_id != sire_id, Error::<T>::SameParentIndex);
+            // 调用者必须拥有自己Kitty的使用权
+            Self::ensure_user(&who, kitty_id)?;
+            // 获取种猫的使用者和配种费，出租期间配种费归租用者
+            let sire_owner = Self::user_of(sire_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            let fee = Self::sire_listings(sire_id).ok_or(Error::<T>::SireNotOffered)?;
+            // 向种猫的所有者支付配种费
+            T::Currency::transfer(&who, &sire_owner, fee, ExistenceRequirement::KeepAlive)?;
+            Self::do_breed(&who, kitty_id, sire_id)?;
+            Self::deposit_event(Event::KittySired(who, sire_owner, sire_id, fee));
+            Ok(())
+        }
+
+        // 承诺创建Kitty并锁定质押，DNA由之后区块的哈希生成，在下一个区块自动揭示
+        #[pallet::weight(T::WeightInfo::commit_create())]
+        #[transactional]
+        pub fn commit_create(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::commit(who, None, false)
+        }
+
+        // 承诺繁殖，父母立即进入冷却期，子代在揭示时生成
+        #[pallet::weight(T::WeightInfo::commit_breed())]
+        #[transactional]
+        pub fn commit_breed(
+            origin: OriginFor<T>,
+            kitty_id_1: T::KittyIndex,
+            kitty_id_2: T::KittyIndex,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(kitty_id_1 != kitty_id_2, Error::<T>::SameParentIndex);
+            Self::ensure_user(&who, kitty_id_1)?;
+            Self::ensure_user(&who, kitty_id_2)?;
+            Self::ensure_can_breed(kitty_id_1, kitty_id_2)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::start_cooldown(kitty_id_1, now);
+            Self::start_cooldown(kitty_id_2, now);
+            Self::commit(who, Some((kitty_id_1, kitty_id_2)), false)
+        }
+
+        // 批量承诺创建，任意一个失败则全部回滚
+        #[pallet::weight(T::WeightInfo::commit_create_batch(*count))]
+        #[transactional]
+        pub fn commit_create_batch(origin: OriginFor<T>, count: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(count <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+            for _ in 0..count {
+                Self::commit(who.clone(), None, false)?;
             }
-            // 创建kitty时质押一定数量的token
-            Self::create_kitty_with_stake(&who, new_dna)
+            Self::deposit_event(Event::BatchCompleted(who, count));
+            Ok(())
+        }
+
+        // 承诺时支付配种费，使用他人出租的种猫繁殖，子代在揭示时生成
+        #[pallet::weight(T::WeightInfo::commit_breed_with_sire())]
+        #[transactional]
+        pub fn commit_breed_with_sire(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            sire_id: T::KittyIndex,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(kitty_id != sire_id, Error::<T>::SameParentIndex);
+            Self::ensure_user(&who, kitty_id)?;
+            // 获取种猫的使用者和配种费，出租期间配种费归租用者
+            let sire_owner = Self::user_of(sire_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            let fee = Self::sire_listings(sire_id).ok_or(Error::<T>::SireNotOffered)?;
+            Self::ensure_can_breed(kitty_id, sire_id)?;
+            T::Currency::transfer(&who, &sire_owner, fee, ExistenceRequirement::KeepAlive)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::start_cooldown(kitty_id, now);
+            Self::start_cooldown(sire_id, now);
+            Self::commit(who.clone(), Some((kitty_id, sire_id)), true)?;
+            Self::deposit_event(Event::KittySired(who, sire_owner, sire_id, fee));
+            Ok(())
+        }
+
+        // 揭示承诺，生成Kitty，任何人都可以调用
+        #[pallet::weight(T::WeightInfo::reveal())]
+        pub fn reveal(origin: OriginFor<T>, commitment_id: u64) -> DispatchResult {
+            ensure_signed(origin)?;
+            let commitment = Self::commitments(commitment_id).ok_or(Error::<T>::CommitmentNotFound)?;
+            ensure!(<frame_system::Pallet<T>>::block_number() > commitment.reveal_at, Error::<T>::RevealTooEarly);
+            Self::reveal_commitment(commitment_id, commitment)
         }
 
         // 卖出
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::sell())]
         pub fn sell(origin: OriginFor<T>, kitty_id: T::KittyIndex, price: Option<BalanceOf<T>>) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            // 校验发起方是否是该kitty的所有者
-            ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
-            // 将Kitty添加到出售列表
-            ListForSale::<T>::insert(kitty_id, price);
-            // 发出卖出事件
-            Self::deposit_event(Event::KittyListed(who, kitty_id, price));
+            Self::do_sell(&who, kitty_id, price, None, None)
+        }
+
+        // 以资产计价出售，买家使用该资产支付
+        #[pallet::weight(T::WeightInfo::sell_for_asset())]
+        pub fn sell_for_asset(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            asset_id: AssetIdOf<T>,
+            price: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            // 资产必须存在，资产的最小余额不为0
+            let min_balance = <T::Assets as fungibles::Inspect<T::AccountId>>::minimum_balance(asset_id);
+            ensure!(!min_balance.is_zero(), Error::<T>::UnknownAsset);
+            // 手续费、版税和卖家所得都不能低于最小余额，否则挂单无法被购买
+            let fee = T::MarketplaceFee::get() * price;
+            let royalty = T::CreatorRoyalty::get() * price;
+            let proceeds = price.saturating_sub(fee).saturating_sub(royalty);
+            ensure!(
+                [fee, royalty, proceeds].iter().all(|part| part.is_zero() || *part >= min_balance),
+                Error::<T>::AssetPriceTooLow
+            );
+            Self::do_sell(&who, kitty_id, Some(price), None, Some(asset_id))
+        }
+
+        // 卖出，挂单在expires区块自动失效
+        #[pallet::weight(T::WeightInfo::sell_with_expiry())]
+        #[transactional]
+        pub fn sell_with_expiry(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            price: BalanceOf<T>,
+            expires: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_sell(&who, kitty_id, Some(price), Some(expires), None)
+        }
+
+        // 取消出售，包括荷兰式拍卖
+        #[pallet::weight(T::WeightInfo::cancel_listing())]
+        pub fn cancel_listing(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&who, kitty_id)?;
+            ensure!(Self::sale_price(kitty_id).is_some(), Error::<T>::KittyNotForSell);
+            Self::clear_listing(kitty_id);
+            Self::deposit_event(Event::ListingCancelled(who, kitty_id));
+            Ok(())
+        }
+
+        // 批量出售，任意一个失败则全部回滚
+        #[pallet::weight(T::WeightInfo::sell_batch(listings.len() as u32))]
+        #[transactional]
+        pub fn sell_batch(
+            origin: OriginFor<T>,
+            listings: BoundedVec<(T::KittyIndex, Option<BalanceOf<T>>), T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let count = listings.len() as u32;
+            for (kitty_id, price) in listings {
+                Self::do_sell(&who, kitty_id, price, None, None)?;
+            }
+            Self::deposit_event(Event::BatchCompleted(who, count));
+            Ok(())
+        }
+
+        // 销毁Kitty并取回质押，由它繁殖的子代不受影响
+        #[pallet::weight(T::WeightInfo::burn(T::MaxOffersPerKitty::get()))]
+        pub fn burn(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&who, kitty_id)?;
+            Self::ensure_not_locked(kitty_id)?;
+            if let Some(kitty) = Kitties::<T>::take(kitty_id) {
+                Self::count_traits(&kitty.dna, false);
+            }
+            Owner::<T>::remove(kitty_id);
+            Creators::<T>::remove(kitty_id);
+            Approvals::<T>::remove(kitty_id);
+            OwnedKitties::<T>::mutate(&who, |ids| ids.retain(|id| *id != kitty_id));
+            // 取消所有出售和出租
+            Self::clear_listing(kitty_id);
+            SireListings::<T>::remove(kitty_id);
+            RentalListings::<T>::remove(kitty_id);
+            // 释放所有报价锁定的金额
+            for offer in Offers::<T>::take(kitty_id) {
+                Self::release_offer(kitty_id, &offer);
+            }
+            // 清除繁殖冷却
+            let state = BreedingStates::<T>::take(kitty_id);
+            CooldownEnds::<T>::remove(state.ready_at, kitty_id);
+            // 解除质押
+            T::Currency::unreserve_named(&KITTY_RESERVE_ID, &who, KittyDeposits::<T>::take(kitty_id));
+            Self::deposit_event(Event::KittyBurned(who, kitty_id));
+            Ok(())
+        }
+
+        // 以荷兰式拍卖出售，价格从start_price经过duration个区块降到end_price
+        #[pallet::weight(T::WeightInfo::sell_dutch())]
+        pub fn sell_dutch(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            start_price: BalanceOf<T>,
+            end_price: BalanceOf<T>,
+            duration: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&who, kitty_id)?;
+            Self::ensure_not_locked(kitty_id)?;
+            ensure!(start_price >= end_price && !duration.is_zero(), Error::<T>::InvalidDutchAuction);
+            let start = <frame_system::Pallet<T>>::block_number();
+            Self::clear_listing(kitty_id);
+            DutchListings::<T>::insert(kitty_id, DutchListing { start_price, end_price, start, duration });
+            Self::deposit_event(Event::KittyListedDutch(who, kitty_id, start_price, end_price, duration));
             Ok(())
         }
 
         // 转移
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::transfer())]
+        #[transactional]
         pub fn transfer(origin: OriginFor<T>, new_owner: T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
             let who = ensure_signed(origin)?;
             // 检查是否是原拥有者
             ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
-            // 更新Kitty的拥有者（双方分别释放和重新质押）
-            // 获取质押金额
-            let stake_amount = T::StakeForEachKitty::get();
-            // 质押新的拥有者一定金额
-            T::Currency::reserve(&new_owner, stake_amount).map_err(|_| Error::<T>::NotEnoughBalanceForStaking)?;
-            // 解除旧拥有者的质押
-            T::Currency::unreserve(&who, stake_amount);
-            // 更新Kitty的所有者为新的拥有者
-            Owner::<T>::insert(kitty_id, Some(new_owner.clone()));
-            // 发布转移事件
-            Self::deposit_event(Event::KittyTransfer(who, new_owner, kitty_id));
+            Self::do_transfer(kitty_id, &who, &new_owner)
+        }
+
+        // 批量转移给同一个账户，任意一个失败则全部回滚
+        #[pallet::weight(T::WeightInfo::transfer_batch(kitty_ids.len() as u32))]
+        #[transactional]
+        pub fn transfer_batch(
+            origin: OriginFor<T>,
+            new_owner: T::AccountId,
+            kitty_ids: BoundedVec<T::KittyIndex, T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let count = kitty_ids.len() as u32;
+            for kitty_id in kitty_ids {
+                Self::ensure_owner(&who, kitty_id)?;
+                Self::do_transfer(kitty_id, &who, &new_owner)?;
+            }
+            Self::deposit_event(Event::BatchCompleted(who, count));
+            Ok(())
+        }
+
+        // 赠送Kitty，接收方确认并质押后才完成转移，赠送期间Kitty不能出售和繁殖
+        #[pallet::weight(T::WeightInfo::gift())]
+        pub fn gift(origin: OriginFor<T>, to: T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&who, kitty_id)?;
+            ensure!(who != to, Error::<T>::GiftToSelf);
+            Self::ensure_not_locked(kitty_id)?;
+            let expires = <frame_system::Pallet<T>>::block_number().saturating_add(T::GiftExpiry::get());
+            // 每个区块失效的赠送数量有上限
+            GiftsExpiringAt::<T>::try_mutate(expires, |ids| ids.try_push(kitty_id))
+                .map_err(|_| Error::<T>::TooManyGiftsExpiring)?;
+            // 赠送期间Kitty不再出售
+            Self::clear_listing(kitty_id);
+            PendingGifts::<T>::insert(kitty_id, Gift { from: who.clone(), to: to.clone(), expires });
+            Self::deposit_event(Event::GiftOffered(who, to, kitty_id, expires));
+            Ok(())
+        }
+
+        // 接收方确认赠送，质押由接收方承担
+        #[pallet::weight(T::WeightInfo::accept_gift())]
+        #[transactional]
+        pub fn accept_gift(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let gift = Self::take_gift(kitty_id)?;
+            ensure!(gift.to == who, Error::<T>::NotGiftRecipient);
+            Self::do_transfer(kitty_id, &gift.from, &who)?;
+            Self::deposit_event(Event::GiftAccepted(gift.from, who, kitty_id));
+            Ok(())
+        }
+
+        // 接收方拒绝赠送，赠送方也可以撤回赠送
+        #[pallet::weight(T::WeightInfo::reject_gift())]
+        #[transactional]
+        pub fn reject_gift(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let gift = Self::take_gift(kitty_id)?;
+            ensure!(gift.to == who || gift.from == who, Error::<T>::NotGiftRecipient);
+            Self::deposit_event(Event::GiftRejected(gift.from, gift.to, kitty_id));
+            Ok(())
+        }
+
+        // 出租Kitty，租用者按区块支付租金，最长租用max_duration个区块
+        #[pallet::weight(T::WeightInfo::list_for_rent())]
+        pub fn list_for_rent(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            price_per_block: BalanceOf<T>,
+            max_duration: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&who, kitty_id)?;
+            Self::ensure_not_locked(kitty_id)?;
+            ensure!(!max_duration.is_zero(), Error::<T>::InvalidLeaseDuration);
+            RentalListings::<T>::insert(kitty_id, RentalListing { price_per_block, max_duration });
+            Self::deposit_event(Event::KittyListedForRent(who, kitty_id, price_per_block, max_duration));
+            Ok(())
+        }
+
+        // 取消出租，不影响正在进行的租约
+        #[pallet::weight(T::WeightInfo::cancel_rent_listing())]
+        pub fn cancel_rent_listing(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&who, kitty_id)?;
+            ensure!(RentalListings::<T>::contains_key(kitty_id), Error::<T>::KittyNotForRent);
+            RentalListings::<T>::remove(kitty_id);
+            Self::deposit_event(Event::RentListingCancelled(who, kitty_id));
+            Ok(())
+        }
+
+        // 预付租金租用Kitty，租期内获得使用权，租期结束后自动归还
+        #[pallet::weight(T::WeightInfo::rent())]
+        #[transactional]
+        pub fn rent(origin: OriginFor<T>, kitty_id: T::KittyIndex, duration: T::BlockNumber) -> DispatchResult {
+            let renter = ensure_signed(origin)?;
+            let owner = Self::owner(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            ensure!(renter != owner, Error::<T>::BuyerIsOwner);
+            let listing = Self::rental_listings(kitty_id).ok_or(Error::<T>::KittyNotForRent)?;
+            Self::ensure_not_locked(kitty_id)?;
+            ensure!(!duration.is_zero() && duration <= listing.max_duration, Error::<T>::InvalidLeaseDuration);
+            // 预付全部租金
+            let blocks: u32 = duration.saturated_into();
+            let rent = listing.price_per_block.saturating_mul(blocks.into());
+            T::Currency::transfer(&renter, &owner, rent, ExistenceRequirement::KeepAlive)?;
+            let end = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+            // 每个区块到期的租约数量有上限
+            LeasesExpiringAt::<T>::try_mutate(end, |ids| ids.try_push(kitty_id))
+                .map_err(|_| Error::<T>::TooManyLeasesExpiring)?;
+            // 租期内Kitty不能出售，所有者出租的种猫也一并取消
+            Self::clear_listing(kitty_id);
+            SireListings::<T>::remove(kitty_id);
+            Leases::<T>::insert(kitty_id, Lease { renter: renter.clone(), end });
+            Self::deposit_event(Event::KittyRented(renter, owner, kitty_id, end, rent));
+            Ok(())
+        }
+
+        // 授权其他账户转移某个Kitty，每个Kitty同时只有一个被授权账户
+        #[pallet::weight(T::WeightInfo::approve())]
+        pub fn approve(origin: OriginFor<T>, approved: T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&who, kitty_id)?;
+            Approvals::<T>::insert(kitty_id, approved.clone());
+            Self::deposit_event(Event::Approval(who, approved, kitty_id));
+            Ok(())
+        }
+
+        // 取消对某个Kitty的授权
+        #[pallet::weight(T::WeightInfo::clear_approval())]
+        pub fn clear_approval(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&who, kitty_id)?;
+            Approvals::<T>::remove(kitty_id);
+            Self::deposit_event(Event::ApprovalCleared(who, kitty_id));
+            Ok(())
+        }
+
+        // 授权或取消授权操作者转移调用者的全部Kitty
+        #[pallet::weight(T::WeightInfo::set_approval_for_all())]
+        pub fn set_approval_for_all(origin: OriginFor<T>, operator: T::AccountId, approved: bool) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            if approved {
+                Operators::<T>::insert(&who, &operator, ());
+            } else {
+                Operators::<T>::remove(&who, &operator);
+            }
+            Self::deposit_event(Event::ApprovalForAll(who, operator, approved));
             Ok(())
         }
 
-        // 购买
-        #[pallet::weight(0)]
+        // 被授权的账户或操作者代替所有者转移Kitty
+        #[pallet::weight(T::WeightInfo::transfer_from())]
+        #[transactional]
+        pub fn transfer_from(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            to: T::AccountId,
+            kitty_id: T::KittyIndex,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&from, kitty_id)?;
+            ensure!(Self::is_approved_or_owner(&who, &from, kitty_id), Error::<T>::NotApproved);
+            Self::do_transfer(kitty_id, &from, &to)
+        }
+
+        // 购买，挂单可能以资产计价
+        #[pallet::weight(T::WeightInfo::buy().max(T::WeightInfo::buy_with_asset()))]
+        #[transactional]
         pub fn buy(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
             let buyer = ensure_signed(origin)?;
-            // 获取Kitty的所有者
-            let seller = Owner::<T>::get(kitty_id).unwrap();
-            // 检查购买者和所有者是否是同一个人
-            ensure!(Some(buyer.clone()) != Some(seller.clone()), Error::<T>::BuyerIsOwner);
-            // 获取Kitty的价格，如果不存在表示Kitty不出售
-            let kitty_price = ListForSale::<T>::get(kitty_id).ok_or(Error::<T>::KittyNotForSell)?;
-            // 获取买家余额
-            let buyer_balance = T::Currency::free_balance(&buyer);
-            // 质押的金额
+            Self::do_buy(buyer, kitty_id, None)
+        }
+
+        // 购买，当前价格高于max_price或计价资产不是asset时失败，防止卖家在购买前抬价或更换计价资产
+        // asset为None表示以原生代币计价
+        #[pallet::weight(match asset {
+            Some(_) => T::WeightInfo::buy_with_asset(),
+            None => T::WeightInfo::buy_at_most(),
+        })]
+        #[transactional]
+        pub fn buy_at_most(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            max_price: BalanceOf<T>,
+            asset: Option<AssetIdOf<T>>,
+        ) -> DispatchResult {
+            let buyer = ensure_signed(origin)?;
+            Self::do_buy(buyer, kitty_id, Some((max_price, asset)))
+        }
+
+        // 对任意Kitty报价，报价金额会被锁定直到报价被接受、撤回或清理
+        #[pallet::weight(T::WeightInfo::make_offer(T::MaxOffersPerKitty::get()))]
+        #[transactional]
+        pub fn make_offer(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            amount: BalanceOf<T>,
+            expires: T::BlockNumber,
+        ) -> DispatchResult {
+            let buyer = ensure_signed(origin)?;
+            let owner = Self::owner(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            ensure!(buyer != owner, Error::<T>::BuyerIsOwner);
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(expires > now, Error::<T>::InvalidOfferExpiry);
+            Offers::<T>::try_mutate(kitty_id, |offers| -> DispatchResult {
+                // 同一买家的新报价替换旧报价，并清理已过期的报价
+                offers.retain(|offer| {
+                    let keep = offer.buyer != buyer && offer.expires > now;
+                    if !keep {
+                        Self::release_offer(kitty_id, offer);
+                    }
+                    keep
+                });
+                // 报价已满时，新报价必须高于最低报价，最低报价被挤出并释放金额
+                if offers.is_full() {
+                    let (index, lowest) = offers
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, offer)| offer.amount)
+                        .ok_or(Error::<T>::TooManyOffers)?;
+                    ensure!(amount > lowest.amount, Error::<T>::TooManyOffers);
+                    let outbid = offers.remove(index);
+                    Self::release_offer(kitty_id, &outbid);
+                    Self::deposit_event(Event::OfferOutbid(outbid.buyer, kitty_id));
+                }
+                T::Currency::reserve_named(&BID_RESERVE_ID, &buyer, amount)
+                    .map_err(|_| Error::<T>::NotEnoughBalanceForBuying)?;
+                // 每个区块失效的报价数量有上限
+                OffersExpiringAt::<T>::try_mutate(expires, |queue| queue.try_push((kitty_id, buyer.clone())))
+                    .map_err(|_| Error::<T>::TooManyOffersExpiring)?;
+                offers
+                    .try_push(Offer { buyer: buyer.clone(), amount, expires })
+                    .map_err(|_| Error::<T>::TooManyOffers)?;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::OfferMade(buyer, kitty_id, amount, expires));
+            Ok(())
+        }
+
+        // 撤回报价，释放锁定的金额
+        #[pallet::weight(T::WeightInfo::withdraw_offer())]
+        pub fn withdraw_offer(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let buyer = ensure_signed(origin)?;
+            Offers::<T>::try_mutate(kitty_id, |offers| -> DispatchResult {
+                let index = offers.iter().position(|offer| offer.buyer == buyer).ok_or(Error::<T>::OfferNotFound)?;
+                let offer = offers.remove(index);
+                Self::release_offer(kitty_id, &offer);
+                Ok(())
+            })?;
+            Self::deposit_event(Event::OfferWithdrawn(buyer, kitty_id));
+            Ok(())
+        }
+
+        // 所有者接受报价，Kitty转给买家，其余报价全部释放
+        #[pallet::weight(T::WeightInfo::accept_offer(T::MaxOffersPerKitty::get()))]
+        #[transactional]
+        pub fn accept_offer(origin: OriginFor<T>, kitty_id: T::KittyIndex, buyer: T::AccountId) -> DispatchResult {
+            let seller = ensure_signed(origin)?;
+            Self::ensure_owner(&seller, kitty_id)?;
+            ensure!(buyer != seller, Error::<T>::BuyerIsOwner);
+            Self::ensure_not_locked(kitty_id)?;
+            let offers = Offers::<T>::take(kitty_id);
+            let offer = offers.iter().find(|offer| offer.buyer == buyer).cloned().ok_or(Error::<T>::OfferNotFound)?;
+            ensure!(offer.expires > <frame_system::Pallet<T>>::block_number(), Error::<T>::OfferExpired);
+            // 释放其他报价和买家报价锁定的金额
+            for released in offers.iter() {
+                Self::release_offer(kitty_id, released);
+            }
+            // 质押新的拥有者一定金额，解除旧拥有者的质押
+            Self::transfer_deposit(kitty_id, &seller, &buyer)?;
+            // 报价金额转给卖家
+            let (fee, royalty) = Self::pay_for_kitty(kitty_id, &buyer, &seller, offer.amount)?;
+            Self::change_owner(kitty_id, &seller, &buyer)?;
+            Self::deposit_event(Event::KittySold(buyer, seller, kitty_id, offer.amount, fee, royalty));
+            Ok(())
+        }
+
+        // 发起英式拍卖，拍卖在end区块结算
+        #[pallet::weight(T::WeightInfo::create_auction())]
+        pub fn create_auction(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            reserve_price: BalanceOf<T>,
+            end: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_owner(&who, kitty_id)?;
+            Self::ensure_not_locked(kitty_id)?;
+            // 结束区块必须在未来
+            ensure!(end > <frame_system::Pallet<T>>::block_number(), Error::<T>::InvalidAuctionEnd);
+            // 每个区块结算的拍卖数量有上限
+            AuctionsEndingAt::<T>::try_mutate(end, |ids| ids.try_push(kitty_id))
+                .map_err(|_| Error::<T>::TooManyAuctionsEnding)?;
+            // 拍卖期间Kitty不再按其他方式出售
+            Self::clear_listing(kitty_id);
+            Auctions::<T>::insert(
+                kitty_id,
+                Auction { seller: who.clone(), reserve_price, end, best_bid: None },
+            );
+            Self::deposit_event(Event::AuctionCreated(who, kitty_id, reserve_price, end));
+            Ok(())
+        }
+
+        // 出价，出价金额和质押金额会被锁定，出价被超过时自动释放
+        #[pallet::weight(T::WeightInfo::bid())]
+        #[transactional]
+        pub fn bid(origin: OriginFor<T>, kitty_id: T::KittyIndex, amount: BalanceOf<T>) -> DispatchResult {
+            let bidder = ensure_signed(origin)?;
+            let mut auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+            ensure!(<frame_system::Pallet<T>>::block_number() < auction.end, Error::<T>::AuctionEnded);
+            ensure!(bidder != auction.seller, Error::<T>::BuyerIsOwner);
+            // 出价不能低于保留价，且必须高于当前最高出价
+            ensure!(amount >= auction.reserve_price, Error::<T>::BidTooLow);
             let stake_amount = T::StakeForEachKitty::get();
-            // 检查买家余额是否足够
-            ensure!(buyer_balance > (kitty_price + stake_amount),Error::<T>::NotEnoughBalanceForBuying);
-            // 质押新的拥有者一定金额
-            T::Currency::reserve(&buyer, stake_amount).map_err(|_| Error::<T>::NotEnoughBalanceForStaking)?;
-            // 解除旧拥有者的质押
-            T::Currency::unreserve(&seller, stake_amount);
-            // 买家向卖家转账
-            T::Currency::transfer(&buyer, &seller, kitty_price, ExistenceRequirement::KeepAlive)?;
-            // 更新Kitty的所有者为买家
-            Owner::<T>::insert(kitty_id, Some(buyer.clone()));
-            // 将Kitty从出售列表中移除
-            ListForSale::<T>::remove(kitty_id);
-            // 发出交易完成事件
-            Self::deposit_event(Event::KittySold(buyer, seller, kitty_id));
+            if let Some((best_bidder, best_amount)) = auction.best_bid.take() {
+                ensure!(amount > best_amount, Error::<T>::BidTooLow);
+                // 释放上一个最高出价者锁定的金额
+                T::Currency::unreserve_named(&BID_RESERVE_ID, &best_bidder, best_amount.saturating_add(stake_amount));
+            }
+            // 锁定出价和赢得拍卖后的质押金额
+            T::Currency::reserve_named(&BID_RESERVE_ID, &bidder, amount.saturating_add(stake_amount))
+                .map_err(|_| Error::<T>::NotEnoughBalanceForBuying)?;
+            auction.best_bid = Some((bidder.clone(), amount));
+            Auctions::<T>::insert(kitty_id, auction);
+            Self::deposit_event(Event::BidPlaced(bidder, kitty_id, amount));
+            Ok(())
+        }
+
+        // 取消没有出价的拍卖
+        #[pallet::weight(T::WeightInfo::cancel_auction())]
+        pub fn cancel_auction(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+            ensure!(auction.seller == who, Error::<T>::NotOwner);
+            ensure!(auction.best_bid.is_none(), Error::<T>::AuctionHasBids);
+            AuctionsEndingAt::<T>::mutate(auction.end, |ids| ids.retain(|id| *id != kitty_id));
+            Auctions::<T>::remove(kitty_id);
+            Self::deposit_event(Event::AuctionCancelled(who, kitty_id));
             Ok(())
         }
     }
 
     impl<T: Config> Pallet<T> {
+        // 获取Kitty当前的售价，荷兰式拍卖的价格随区块高度下降
+        pub fn sale_price(kitty_id: T::KittyIndex) -> Option<BalanceOf<T>> {
+            Self::kitties_list_for_sales(kitty_id).or_else(|| {
+                Self::dutch_listings(kitty_id)
+                    .map(|listing| listing.price_at(<frame_system::Pallet<T>>::block_number()))
+            })
+        }
+
+        // 解析Kitty的DNA得到它的性状
+        pub fn kitty_traits(kitty_id: T::KittyIndex) -> Option<KittyTraits> {
+            Self::kitties(kitty_id).map(|kitty| KittyTraits::from_dna(&kitty.dna))
+        }
+
+        // Kitty的详细信息
+        pub fn kitty_info(kitty_id: T::KittyIndex) -> Option<KittyInfoOf<T>> {
+            let kitty = Self::kitties(kitty_id)?;
+            let owner = Self::owner(kitty_id)?;
+            let price = Self::sale_price(kitty_id);
+            let lease = Self::leases(kitty_id);
+            Some(KittyInfo {
+                id: kitty_id,
+                owner,
+                user: lease.as_ref().map(|lease| lease.renter.clone()),
+                lease_end: lease.map(|lease| lease.end),
+                dna: kitty.dna,
+                parents: kitty.parents,
+                generation: kitty.generation,
+                birth_block: kitty.birth_block,
+                traits: KittyTraits::from_dna(&kitty.dna),
+                price,
+                price_asset: price.and(Self::listing_asset(kitty_id)),
+                listing_expires: price.and(Self::listing_expiry(kitty_id)),
+                ready_at: Self::breeding_state(kitty_id).ready_at,
+            })
+        }
+
+        // 账户拥有的Kitty
+        pub fn kitties_owned_by(who: &T::AccountId) -> Vec<T::KittyIndex> {
+            Self::owned_kitties(who).into_inner()
+        }
+
+        // 正在出售的Kitty，包括荷兰式拍卖，page从0开始，每页LISTINGS_PAGE_SIZE个
+        pub fn listings(page: u32) -> Vec<KittyInfoOf<T>> {
+            ListForSale::<T>::iter()
+                .filter_map(|(kitty_id, price)| price.map(|_| kitty_id))
+                .chain(DutchListings::<T>::iter_keys())
+                .skip(page.saturating_mul(LISTINGS_PAGE_SIZE) as usize)
+                .take(LISTINGS_PAGE_SIZE as usize)
+                .filter_map(Self::kitty_info)
+                .collect()
+        }
+
+        // 已创建的Kitty数量，包括已销毁的，也是下一个Kitty的ID
+        pub fn kitty_count() -> T::KittyIndex {
+            Self::kitty_cnt().unwrap_or_else(|| 0u32.into())
+        }
+
+        // 每个等位基因对应的现存Kitty数量
+        pub fn trait_counts(gene: u8) -> Vec<u32> {
+            (0..16).map(|allele| TraitCounts::<T>::get(gene, allele)).collect()
+        }
+
+        // 稀有度评分，每个性状的评分为表现该性状的Kitty占比的倒数乘以100，性状越稀有评分越高
+        pub fn rarity_score(kitty_id: T::KittyIndex) -> Option<u32> {
+            let kitty = Self::kitties(kitty_id)?;
+            let score = genes::TRAIT_GENES.iter().fold(0u32, |score, &gene| {
+                let total = TraitCounts::<T>::iter_prefix_values(gene as u8).fold(0u32, u32::saturating_add);
+                let count = TraitCounts::<T>::get(gene as u8, genes::dominant(&kitty.dna, gene)).max(1);
+                score.saturating_add(total.saturating_mul(100) / count)
+            });
+            Some(score)
+        }
+
+        // 更新性状统计，added为false时表示Kitty被销毁
+        pub(crate) fn count_traits(dna: &[u8; 16], added: bool) {
+            for gene in genes::TRAIT_GENES {
+                TraitCounts::<T>::mutate(gene as u8, genes::dominant(dna, gene), |count| {
+                    *count = if added { count.saturating_add(1) } else { count.saturating_sub(1) }
+                });
+            }
+        }
+
         // 随机生成DNA
         fn random_value(sender: &T::AccountId) -> [u8; 16] {
             let payload = (
@@ -193,7 +1412,12 @@ pub mod pallet {
         }
 
         // 创建kitty时质押一定数量的token
-        fn create_kitty_with_stake(owner: &T::AccountId, dna: [u8; 16]) -> DispatchResult {
+        pub(crate) fn create_kitty_with_stake(
+            owner: &T::AccountId,
+            dna: [u8; 16],
+            parents: Option<(T::KittyIndex, T::KittyIndex)>,
+            generation: u32,
+        ) -> DispatchResult {
             // 获取当前的kitty_id
             let kitty_id = match Self::kitty_cnt() {
                 Some(id) => {
@@ -202,21 +1426,463 @@ pub mod pallet {
                 }
                 None => 0u32.into(),
             };
+            // 检查创建者拥有的Kitty数量是否已达上限
+            ensure!(
+                (Self::owned_kitties(owner).len() as u32) < T::MaxKittiesOwned::get(),
+                Error::<T>::TooManyOwned
+            );
             // 获取质押的金额
             let stake_amount = T::StakeForEachKitty::get();
             // 质押创建者一定的金额,当余额不足时抛出NotEnoughBalanceForStaking异常
-            T::Currency::reserve(&owner, stake_amount).map_err(|_| Error::<T>::NotEnoughBalanceForStaking)?;
+            T::Currency::reserve_named(&KITTY_RESERVE_ID, owner, stake_amount)
+                .map_err(|_| Error::<T>::NotEnoughBalanceForStaking)?;
+            KittyDeposits::<T>::insert(kitty_id, stake_amount);
             // 将Kitty加入Kitties集合
-            Kitties::<T>::insert(kitty_id, Some(Kitty { dna }));
+            let kitty = Kitty {
+                dna,
+                parents,
+                generation,
+                birth_block: <frame_system::Pallet<T>>::block_number(),
+                gender: Gender::from_dna(&dna),
+            };
+            Kitties::<T>::insert(kitty_id, Some(kitty));
+            Self::count_traits(&dna, true);
             // log
             log::info!("🎈😺 A kitty is born with ID ➡ {:?}.", kitty_id);
             // 为Kitty绑定所有人
             Owner::<T>::insert(kitty_id, Some(owner.clone()));
+            Creators::<T>::insert(kitty_id, owner);
+            OwnedKitties::<T>::try_mutate(owner, |ids| ids.try_push(kitty_id))
+                .map_err(|_| Error::<T>::TooManyOwned)?;
             // 更新当前的kitty_id
             KittyCnt::<T>::put(kitty_id + 1u32.into());
             // 发布创建事件
             Self::deposit_event(Event::KittyCreate(owner.clone(), kitty_id));
             Ok(())
         }
+
+        // 校验Kitty存在且属于who
+        fn ensure_owner(who: &T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+            let owner = Self::owner(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            ensure!(owner == *who, Error::<T>::NotOwner);
+            Ok(())
+        }
+
+        // 获取Kitty当前的使用者，出租期间为租用者，否则为所有者
+        pub fn user_of(kitty_id: T::KittyIndex) -> Option<T::AccountId> {
+            Self::leases(kitty_id).map(|lease| lease.renter).or_else(|| Self::owner(kitty_id))
+        }
+
+        // 校验Kitty存在且who拥有其使用权
+        fn ensure_user(who: &T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+            let user = Self::user_of(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            ensure!(user == *who, Error::<T>::NotOwner);
+            Ok(())
+        }
+
+        // 租期结束，使用权归还所有者
+        fn end_lease(kitty_id: T::KittyIndex) {
+            if let Some(lease) = Leases::<T>::take(kitty_id) {
+                // 租用者出租的种猫随租约一起结束
+                SireListings::<T>::remove(kitty_id);
+                Self::deposit_event(Event::LeaseEnded(lease.renter, kitty_id));
+            }
+        }
+
+        // 检查who是否可以转移owner的Kitty
+        pub fn is_approved_or_owner(who: &T::AccountId, owner: &T::AccountId, kitty_id: T::KittyIndex) -> bool {
+            who == owner ||
+                Self::approvals(kitty_id).as_ref() == Some(who) ||
+                Operators::<T>::contains_key(owner, who)
+        }
+
+        // 按当前价格购买，max_price为买家可以接受的最高价格
+        // limit为买家可接受的(最高价格, 计价资产)
+        fn do_buy(
+            buyer: T::AccountId,
+            kitty_id: T::KittyIndex,
+            limit: Option<(BalanceOf<T>, Option<AssetIdOf<T>>)>,
+        ) -> DispatchResult {
+            // 获取Kitty的所有者
+            let seller = Self::owner(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            // 检查购买者和所有者是否是同一个人
+            ensure!(buyer != seller, Error::<T>::BuyerIsOwner);
+            // 获取Kitty的价格，如果不存在表示Kitty不出售
+            let kitty_price = Self::sale_price(kitty_id).ok_or(Error::<T>::KittyNotForSell)?;
+            // 以资产计价时价款使用资产支付
+            let asset = Self::listing_asset(kitty_id);
+            if let Some((max_price, expected_asset)) = limit {
+                ensure!(asset == expected_asset, Error::<T>::ListingAssetMismatch);
+                ensure!(kitty_price <= max_price, Error::<T>::PriceTooHigh);
+            }
+            // 获取买家余额
+            let buyer_balance = T::Currency::free_balance(&buyer);
+            // 质押的金额
+            let stake_amount = T::StakeForEachKitty::get();
+            // 检查买家余额是否足够
+            let native_amount = if asset.is_some() { stake_amount } else { kitty_price + stake_amount };
+            ensure!(buyer_balance > native_amount, Error::<T>::NotEnoughBalanceForBuying);
+            // 质押新的拥有者一定金额，解除旧拥有者的质押
+            Self::transfer_deposit(kitty_id, &seller, &buyer)?;
+            // 买家向卖家转账，并支付手续费和版税
+            let (fee, royalty) = match asset {
+                Some(asset) => Self::pay_for_kitty_in_asset(asset, kitty_id, &buyer, &seller, kitty_price)?,
+                None => Self::pay_for_kitty(kitty_id, &buyer, &seller, kitty_price)?,
+            };
+            // 更新Kitty的所有者为买家，同时将Kitty从出售列表中移除
+            Self::change_owner(kitty_id, &seller, &buyer)?;
+            // 发出交易完成事件
+            match asset {
+                Some(asset) => Self::deposit_event(Event::KittySoldForAsset(
+                    buyer,
+                    seller,
+                    kitty_id,
+                    asset,
+                    kitty_price,
+                    fee,
+                    royalty,
+                )),
+                None => Self::deposit_event(Event::KittySold(buyer, seller, kitty_id, kitty_price, fee, royalty)),
+            }
+            Ok(())
+        }
+
+        // 挂单出售，price为None时取消出售，expires为None时挂单不会失效，asset为None时以原生代币计价
+        fn do_sell(
+            who: &T::AccountId,
+            kitty_id: T::KittyIndex,
+            price: Option<BalanceOf<T>>,
+            expires: Option<T::BlockNumber>,
+            asset: Option<AssetIdOf<T>>,
+        ) -> DispatchResult {
+            // 校验发起方是否是该kitty的所有者
+            ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+            Self::ensure_not_locked(kitty_id)?;
+            if let Some(expires) = expires {
+                ensure!(expires > <frame_system::Pallet<T>>::block_number(), Error::<T>::InvalidListingExpiry);
+            }
+            // 新的挂单替换原有的挂单
+            Self::clear_listing(kitty_id);
+            if let Some(expires) = expires {
+                // 每个区块失效的挂单数量有上限
+                ListingsExpiringAt::<T>::try_mutate(expires, |ids| ids.try_push(kitty_id))
+                    .map_err(|_| Error::<T>::TooManyListingsExpiring)?;
+                ListingExpiry::<T>::insert(kitty_id, expires);
+            }
+            // 将Kitty添加到出售列表
+            ListForSale::<T>::insert(kitty_id, price);
+            // 发出卖出事件
+            match (asset, price) {
+                (Some(asset), Some(price)) => {
+                    ListingAsset::<T>::insert(kitty_id, asset);
+                    Self::deposit_event(Event::KittyListedForAsset(who.clone(), kitty_id, asset, price));
+                }
+                _ => Self::deposit_event(Event::KittyListed(who.clone(), kitty_id, price)),
+            }
+            Ok(())
+        }
+
+        // 取消出售和荷兰式拍卖，同时从挂单失效队列中移除
+        fn clear_listing(kitty_id: T::KittyIndex) {
+            ListForSale::<T>::remove(kitty_id);
+            ListingAsset::<T>::remove(kitty_id);
+            DutchListings::<T>::remove(kitty_id);
+            if let Some(expires) = ListingExpiry::<T>::take(kitty_id) {
+                ListingsExpiringAt::<T>::mutate(expires, |ids| ids.retain(|id| *id != kitty_id));
+            }
+        }
+
+        // 转移Kitty，调用前需要校验转移的权限
+        fn do_transfer(kitty_id: T::KittyIndex, from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+            Self::ensure_not_locked(kitty_id)?;
+            // 更新Kitty的拥有者（双方分别释放和重新质押）
+            Self::transfer_deposit(kitty_id, from, to)?;
+            // 更新Kitty的所有者为新的拥有者
+            Self::change_owner(kitty_id, from, to)?;
+            // 发布转移事件
+            Self::deposit_event(Event::KittyTransfer(from.clone(), to.clone(), kitty_id));
+            Ok(())
+        }
+
+        // 校验Kitty没有被拍卖、赠送等流程锁定
+        fn ensure_not_locked(kitty_id: T::KittyIndex) -> DispatchResult {
+            ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+            ensure!(!PendingGifts::<T>::contains_key(kitty_id), Error::<T>::KittyGiftPending);
+            ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+            Ok(())
+        }
+
+        // 释放报价锁定的金额，同时从失效队列中移除
+        fn release_offer(kitty_id: T::KittyIndex, offer: &OfferOf<T>) {
+            T::Currency::unreserve_named(&BID_RESERVE_ID, &offer.buyer, offer.amount);
+            OffersExpiringAt::<T>::mutate(offer.expires, |queue| {
+                queue.retain(|(id, buyer)| !(*id == kitty_id && *buyer == offer.buyer))
+            });
+        }
+
+        // 取出待确认的赠送，同时从失效队列中移除
+        fn take_gift(kitty_id: T::KittyIndex) -> Result<GiftOf<T>, DispatchError> {
+            let gift = PendingGifts::<T>::take(kitty_id).ok_or(Error::<T>::GiftNotFound)?;
+            GiftsExpiringAt::<T>::mutate(gift.expires, |ids| ids.retain(|id| *id != kitty_id));
+            Ok(gift)
+        }
+
+        // 结算拍卖，赢家的出价转给卖家，Kitty转给赢家
+        fn settle_auction(kitty_id: T::KittyIndex) {
+            let auction = match Auctions::<T>::take(kitty_id) {
+                Some(auction) => auction,
+                None => return,
+            };
+            match auction.best_bid {
+                Some((winner, amount)) => {
+                    match Self::do_settle_auction(kitty_id, &auction.seller, &winner, amount) {
+                        Ok((fee, royalty)) => {
+                            Self::deposit_event(Event::AuctionSettled(
+                                winner.clone(),
+                                auction.seller.clone(),
+                                kitty_id,
+                                amount,
+                            ));
+                            Self::deposit_event(Event::KittySold(winner, auction.seller, kitty_id, amount, fee, royalty));
+                        }
+                        Err(e) => {
+                            // 结算失败时退还赢家锁定的金额
+                            log::warn!("🐱 Failed to settle auction for kitty {:?}: {:?}", kitty_id, e);
+                            T::Currency::unreserve_named(
+                                &BID_RESERVE_ID,
+                                &winner,
+                                amount.saturating_add(T::StakeForEachKitty::get()),
+                            );
+                            Self::deposit_event(Event::AuctionCancelled(auction.seller, kitty_id));
+                        }
+                    }
+                }
+                None => Self::deposit_event(Event::AuctionCancelled(auction.seller, kitty_id)),
+            }
+        }
+
+        #[transactional]
+        fn do_settle_auction(
+            kitty_id: T::KittyIndex,
+            seller: &T::AccountId,
+            winner: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+            // 释放赢家锁定的出价和质押金额，出价转给卖家
+            T::Currency::unreserve_named(&BID_RESERVE_ID, winner, amount.saturating_add(T::StakeForEachKitty::get()));
+            let paid = Self::pay_for_kitty(kitty_id, winner, seller, amount)?;
+            // 质押赢家，解除卖家的质押
+            Self::transfer_deposit(kitty_id, seller, winner)?;
+            Self::change_owner(kitty_id, seller, winner)?;
+            Ok(paid)
+        }
+
+        // 新所有者按当前的质押金额质押，旧所有者取回实际质押的金额
+        fn transfer_deposit(kitty_id: T::KittyIndex, from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+            let stake_amount = T::StakeForEachKitty::get();
+            T::Currency::reserve_named(&KITTY_RESERVE_ID, to, stake_amount)
+                .map_err(|_| Error::<T>::NotEnoughBalanceForStaking)?;
+            T::Currency::unreserve_named(&KITTY_RESERVE_ID, from, Self::kitty_deposits(kitty_id));
+            KittyDeposits::<T>::insert(kitty_id, stake_amount);
+            Ok(())
+        }
+
+        // 买家向卖家支付价款，其中手续费交给OnMarketplaceFee处理，版税支付给Kitty的创建者，返回(手续费, 版税)
+        fn pay_for_kitty(
+            kitty_id: T::KittyIndex,
+            buyer: &T::AccountId,
+            seller: &T::AccountId,
+            price: BalanceOf<T>,
+        ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+            let fee = T::MarketplaceFee::get() * price;
+            if !fee.is_zero() {
+                let imbalance =
+                    T::Currency::withdraw(buyer, fee, WithdrawReasons::TRANSFER, ExistenceRequirement::KeepAlive)?;
+                T::OnMarketplaceFee::on_unbalanced(imbalance);
+            }
+            // 创建者自己出售时不收取版税，创建者无法收款时版税归卖家
+            let royalty = match Self::creators(kitty_id) {
+                Some(creator) if creator != *seller => {
+                    let royalty = T::CreatorRoyalty::get() * price;
+                    match T::Currency::transfer(buyer, &creator, royalty, ExistenceRequirement::KeepAlive) {
+                        Ok(()) => royalty,
+                        Err(_) => Zero::zero(),
+                    }
+                }
+                _ => Zero::zero(),
+            };
+            T::Currency::transfer(
+                buyer,
+                seller,
+                price.saturating_sub(fee).saturating_sub(royalty),
+                ExistenceRequirement::KeepAlive,
+            )?;
+            Ok((fee, royalty))
+        }
+
+        // 买家使用资产支付价款，手续费转给AssetFeeCollector，版税支付给Kitty的创建者，返回(手续费, 版税)
+        fn pay_for_kitty_in_asset(
+            asset: AssetIdOf<T>,
+            kitty_id: T::KittyIndex,
+            buyer: &T::AccountId,
+            seller: &T::AccountId,
+            price: BalanceOf<T>,
+        ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+            let fee = T::MarketplaceFee::get() * price;
+            if !fee.is_zero() {
+                <T::Assets as fungibles::Transfer<_>>::transfer(asset, buyer, &T::AssetFeeCollector::get(), fee, true)?;
+            }
+            // 创建者自己出售时不收取版税，创建者无法收款时版税归卖家
+            let royalty = match Self::creators(kitty_id) {
+                Some(creator) if creator != *seller => {
+                    let royalty = T::CreatorRoyalty::get() * price;
+                    match <T::Assets as fungibles::Transfer<_>>::transfer(asset, buyer, &creator, royalty, true) {
+                        Ok(_) => royalty,
+                        Err(_) => Zero::zero(),
+                    }
+                }
+                _ => Zero::zero(),
+            };
+            <T::Assets as fungibles::Transfer<_>>::transfer(
+                asset,
+                buyer,
+                seller,
+                price.saturating_sub(fee).saturating_sub(royalty),
+                true,
+            )?;
+            Ok((fee, royalty))
+        }
+
+        // 繁殖，调用前需要校验父母的使用权
+        fn do_breed(who: &T::AccountId, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> DispatchResult {
+            let (kitty1, kitty2) = Self::ensure_can_breed(kitty_id_1, kitty_id_2)?;
+            let seed = Self::random_value(who);
+            Self::breed_child(who, (kitty_id_1, &kitty1), (kitty_id_2, &kitty2), &seed)?;
+            // 父母进入冷却期
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::start_cooldown(kitty_id_1, now);
+            Self::start_cooldown(kitty_id_2, now);
+            Ok(())
+        }
+
+        // 校验两个Kitty可以繁殖，返回父母
+        fn ensure_can_breed(
+            kitty_id_1: T::KittyIndex,
+            kitty_id_2: T::KittyIndex,
+        ) -> Result<(KittyOf<T>, KittyOf<T>), DispatchError> {
+            // 获取Kitty1
+            let kitty1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyIndex)?;
+            // 获取Kitty2
+            let kitty2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyIndex)?;
+            // 父母的性别必须不同
+            ensure!(kitty1.gender != kitty2.gender, Error::<T>::SameGender);
+            // 赠送中的Kitty不能繁殖
+            ensure!(
+                !PendingGifts::<T>::contains_key(kitty_id_1) && !PendingGifts::<T>::contains_key(kitty_id_2),
+                Error::<T>::KittyGiftPending
+            );
+            // 父母都不能处于冷却期
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(Self::breeding_state(kitty_id_1).ready_at <= now, Error::<T>::KittyOnCooldown);
+            ensure!(Self::breeding_state(kitty_id_2).ready_at <= now, Error::<T>::KittyOnCooldown);
+            Ok((kitty1, kitty2))
+        }
+
+        // 由父母和随机种子生成子代
+        fn breed_child(
+            who: &T::AccountId,
+            (kitty_id_1, kitty1): (T::KittyIndex, &KittyOf<T>),
+            (kitty_id_2, kitty2): (T::KittyIndex, &KittyOf<T>),
+            seed: &[u8; 16],
+        ) -> DispatchResult {
+            // 按显性和隐性基因遗传，并可能发生变异
+            let new_dna = genes::mix(&kitty1.dna, &kitty2.dna, seed, T::MutationChance::get());
+            // 子代的代数为父母中较大的代数加一
+            let generation = kitty1.generation.max(kitty2.generation).saturating_add(1);
+            // 创建kitty时质押一定数量的token
+            Self::create_kitty_with_stake(who, new_dna, Some((kitty_id_1, kitty_id_2)), generation)
+        }
+
+        // 锁定质押并记录承诺
+        fn commit(
+            who: T::AccountId,
+            parents: Option<(T::KittyIndex, T::KittyIndex)>,
+            hired_sire: bool,
+        ) -> DispatchResult {
+            let deposit = T::StakeForEachKitty::get();
+            let reveal_at = <frame_system::Pallet<T>>::block_number().saturating_add(T::RevealDelay::get());
+            let expires = reveal_at.saturating_add(T::RevealWindow::get());
+            let commitment_id = NextCommitmentId::<T>::get();
+            // reveal_at的区块哈希在下一个区块初始化时可用，每个区块揭示和失效的承诺数量有上限
+            CommitmentsRevealingAt::<T>::try_mutate(reveal_at.saturating_add(1u32.into()), |ids| ids.try_push(commitment_id))
+                .map_err(|_| Error::<T>::TooManyCommitmentsRevealing)?;
+            CommitmentsExpiringAt::<T>::try_mutate(expires, |ids| ids.try_push(commitment_id))
+                .map_err(|_| Error::<T>::TooManyCommitmentsExpiring)?;
+            T::Currency::reserve_named(&KITTY_RESERVE_ID, &who, deposit)
+                .map_err(|_| Error::<T>::NotEnoughBalanceForStaking)?;
+            NextCommitmentId::<T>::put(commitment_id.wrapping_add(1));
+            let commitment = Commitment { owner: who.clone(), parents, hired_sire, reveal_at, expires, deposit };
+            Commitments::<T>::insert(commitment_id, commitment);
+            Self::deposit_event(Event::MintCommitted(who, commitment_id, reveal_at));
+            Ok(())
+        }
+
+        // 揭示承诺，生成Kitty，失败时回滚，承诺保留到失效
+        #[transactional]
+        fn reveal_commitment(commitment_id: u64, commitment: CommitmentOf<T>) -> DispatchResult {
+            Commitments::<T>::remove(commitment_id);
+            CommitmentsExpiringAt::<T>::mutate(commitment.expires, |ids| ids.retain(|id| *id != commitment_id));
+            // 质押转为新Kitty的质押
+            T::Currency::unreserve_named(&KITTY_RESERVE_ID, &commitment.owner, commitment.deposit);
+            // 承诺时无法预知的区块哈希，混入揭示时的随机数
+            let seed = (
+                commitment_id,
+                &commitment.owner,
+                <frame_system::Pallet<T>>::block_hash(commitment.reveal_at),
+                T::Randomness::random(&commitment_id.encode()).0,
+            )
+                .using_encoded(blake2_128);
+            match commitment.parents {
+                Some((kitty_id_1, kitty_id_2)) => {
+                    // 承诺后父母可能已被转让或出租，揭示时承诺者仍需拥有父母的使用权
+                    Self::ensure_user(&commitment.owner, kitty_id_1)?;
+                    if !commitment.hired_sire {
+                        Self::ensure_user(&commitment.owner, kitty_id_2)?;
+                    }
+                    let kitty1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyIndex)?;
+                    let kitty2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyIndex)?;
+                    Self::breed_child(&commitment.owner, (kitty_id_1, &kitty1), (kitty_id_2, &kitty2), &seed)?;
+                }
+                None => Self::create_kitty_with_stake(&commitment.owner, genes::base_dna(seed), None, 0)?,
+            }
+            Self::deposit_event(Event::CommitmentRevealed(commitment.owner, commitment_id));
+            Ok(())
+        }
+
+        // 繁殖后进入冷却期，冷却时间随繁殖次数翻倍
+        fn start_cooldown(kitty_id: T::KittyIndex, now: T::BlockNumber) {
+            let mut state = Self::breeding_state(kitty_id);
+            let factor: T::BlockNumber = (1u32 << state.breed_count.min(MAX_COOLDOWN_DOUBLINGS)).into();
+            state.ready_at = now.saturating_add(T::BreedCooldown::get().saturating_mul(factor));
+            state.breed_count = state.breed_count.saturating_add(1);
+            if state.ready_at > now {
+                CooldownEnds::<T>::insert(state.ready_at, kitty_id, ());
+            }
+            BreedingStates::<T>::insert(kitty_id, state);
+        }
+
+        // 变更Kitty的所有者，同时维护账户的Kitty索引
+        fn change_owner(kitty_id: T::KittyIndex, from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+            OwnedKitties::<T>::mutate(from, |ids| ids.retain(|id| *id != kitty_id));
+            OwnedKitties::<T>::try_mutate(to, |ids| ids.try_push(kitty_id))
+                .map_err(|_| Error::<T>::TooManyOwned)?;
+            Owner::<T>::insert(kitty_id, Some(to.clone()));
+            // 所有者变更后取消授权、出售、出租和种猫出租
+            Approvals::<T>::remove(kitty_id);
+            Self::clear_listing(kitty_id);
+            RentalListings::<T>::remove(kitty_id);
+            SireListings::<T>::remove(kitty_id);
+            Ok(())
+        }
     }
 }
\ No newline at end of file
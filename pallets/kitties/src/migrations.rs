@@ -0,0 +1,135 @@
+//! Storage migrations for the kitties pallet.
+
+use super::*;
+use codec::Decode;
+use frame_support::{
+	sp_runtime::traits::{Saturating, Zero},
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+
+pub mod v1 {
+	use super::*;
+
+	// 版本0中的Kitty只包含DNA
+	#[derive(Decode)]
+	struct OldKitty {
+		dna: [u8; 16],
+	}
+
+	// 把只有DNA的Kitty迁移为完整的Kitty记录
+	// 已有Kitty的父母、代数和出生区块未知，按第0代、第0区块出生记录，性别由DNA推出
+	pub fn migrate<T: Config>() -> Weight {
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated = 0u64;
+		Kitties::<T>::translate::<Option<OldKitty>, _>(|_, old| {
+			translated += 1;
+			Some(old.map(|old| Kitty {
+				dna: old.dna,
+				parents: None,
+				generation: 0,
+				birth_block: Zero::zero(),
+				gender: Gender::from_dna(&old.dna),
+			}))
+		});
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log::info!("🐱 Migrated {} kitties to storage version 1.", translated);
+
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+pub mod v2 {
+	use super::*;
+	use frame_support::traits::{NamedReservableCurrency, ReservableCurrency};
+
+	// 把Kitty质押、拍卖出价和报价从普通锁定迁移到本模块的命名锁定，
+	// 记录每个Kitty实际锁定的质押金额，并把已有的Kitty加入OwnedKitties索引
+	pub fn migrate<T: Config>() -> Weight {
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version >= 2 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let stake_amount = T::StakeForEachKitty::get();
+		let mut reads = 1u64;
+		let mut writes = 1u64;
+
+		// 旧版本的质押使用普通锁定，只迁移实际锁定的部分
+		for (kitty_id, owner) in Owner::<T>::iter() {
+			reads += 1;
+			if let Some(owner) = owner {
+				let deposit = stake_amount.saturating_sub(T::Currency::unreserve(&owner, stake_amount));
+				if T::Currency::reserve_named(&KITTY_RESERVE_ID, &owner, deposit).is_err() {
+					log::warn!("🐱 Failed to move the deposit of kitty {:?} to a named reserve.", kitty_id);
+				}
+				KittyDeposits::<T>::insert(kitty_id, deposit);
+				// 旧版本没有账户的Kitty索引，超出上限的Kitty仍归所有者但不计入索引
+				let indexed = OwnedKitties::<T>::try_mutate(&owner, |ids| {
+					if ids.contains(&kitty_id) {
+						Ok(())
+					} else {
+						ids.try_push(kitty_id)
+					}
+				});
+				if indexed.is_err() {
+					log::warn!("🐱 Kitty {:?} exceeds the owner's MaxKittiesOwned and is not indexed.", kitty_id);
+				}
+				reads += 1;
+				writes += 4;
+			}
+		}
+
+		// 拍卖的最高出价包含出价金额和质押金额
+		for (_, auction) in Auctions::<T>::iter() {
+			reads += 1;
+			if let Some((bidder, amount)) = auction.best_bid {
+				let amount = amount.saturating_add(stake_amount);
+				let moved = amount.saturating_sub(T::Currency::unreserve(&bidder, amount));
+				let _ = T::Currency::reserve_named(&BID_RESERVE_ID, &bidder, moved);
+				writes += 2;
+			}
+		}
+
+		for (_, offers) in Offers::<T>::iter() {
+			reads += 1;
+			for offer in offers {
+				let moved = offer.amount.saturating_sub(T::Currency::unreserve(&offer.buyer, offer.amount));
+				let _ = T::Currency::reserve_named(&BID_RESERVE_ID, &offer.buyer, moved);
+				writes += 2;
+			}
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+		log::info!("🐱 Migrated kitty deposits to named reserves.");
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	// 根据已有的Kitty建立TraitCounts统计
+	pub fn migrate<T: Config>() -> Weight {
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version >= 3 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut counted = 0u64;
+		for kitty in Kitties::<T>::iter_values().flatten() {
+			counted += 1;
+			Pallet::<T>::count_traits(&kitty.dna, true);
+		}
+		StorageVersion::new(3).put::<Pallet<T>>();
+		log::info!("🐱 Counted the traits of {} kitties.", counted);
+
+		let genes = crate::genes::TRAIT_GENES.len() as u64;
+		T::DbWeight::get().reads_writes(1 + counted * (1 + genes), 1 + counted * genes)
+	}
+}
@@ -1,10 +1,14 @@
 use crate as pallet_kitties;
-use frame_support::parameter_types;
+use frame_support::{
+	parameter_types,
+	traits::{Currency, GenesisBuild, OnFinalize, OnInitialize, OnUnbalanced},
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -19,9 +23,10 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		SubstrateKitties: pallet_kitties::{Pallet, Call, Storage, Event<T>},
+		SubstrateKitties: pallet_kitties::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -59,11 +64,12 @@ impl system::Config for Test {
 parameter_types! {
 	pub const ExistentialDeposit: u128 = 500;
 	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
 }
 
 impl pallet_balances::Config for Test {
 	type MaxLocks = MaxLocks;
-	type MaxReserves = ();
+	type MaxReserves = MaxReserves;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = Balance;
 	type Event = Event;
@@ -75,8 +81,62 @@ impl pallet_balances::Config for Test {
 
 impl pallet_randomness_collective_flip::Config for Test {}
 
+parameter_types! {
+	pub const AssetDeposit: u128 = 1;
+	pub const ApprovalDeposit: u128 = 1;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: u128 = 1;
+	pub const MetadataDepositPerByte: u128 = 1;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const StakeForEachKitty: u128 = 10_000;
+	pub const MaxKittiesOwned: u32 = 5;
+	pub const BreedCooldown: u64 = 10;
+	pub const MaxAuctionsPerBlock: u32 = 2;
+	pub const MaxOffersPerKitty: u32 = 2;
+	pub const MarketplaceFee: Permill = Permill::from_percent(10);
+	pub const CreatorRoyalty: Permill = Permill::from_percent(5);
+	pub const GiftExpiry: u64 = 5;
+	pub const MaxGiftsPerBlock: u32 = 2;
+	pub const MaxLeasesPerBlock: u32 = 2;
+	pub const MaxBatchSize: u32 = 3;
+	pub const MaxListingsPerBlock: u32 = 2;
+	pub const MutationChance: Permill = Permill::from_percent(10);
+	pub const RevealDelay: u64 = 2;
+	pub const RevealWindow: u64 = 5;
+	pub const MaxCommitmentsPerBlock: u32 = 2;
+	pub static AllowInstantMint: bool = true;
+	pub const MaxOffersPerBlock: u32 = 2;
+}
+
+pub const FEE_ACCOUNT: u64 = 99;
+
+parameter_types! {
+	pub const AssetFeeCollector: u64 = FEE_ACCOUNT;
+}
+
+pub struct MarketplaceFeeToAccount;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for MarketplaceFeeToAccount {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&FEE_ACCOUNT, amount);
+	}
 }
 
 impl pallet_kitties::Config for Test {
@@ -85,6 +145,27 @@ impl pallet_kitties::Config for Test {
 	type KittyIndex = u32;
 	type StakeForEachKitty = StakeForEachKitty;
 	type Currency = Balances;
+	type MaxKittiesOwned = MaxKittiesOwned;
+	type BreedCooldown = BreedCooldown;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MaxOffersPerKitty = MaxOffersPerKitty;
+	type MarketplaceFee = MarketplaceFee;
+	type CreatorRoyalty = CreatorRoyalty;
+	type OnMarketplaceFee = MarketplaceFeeToAccount;
+	type GiftExpiry = GiftExpiry;
+	type MaxGiftsPerBlock = MaxGiftsPerBlock;
+	type MaxLeasesPerBlock = MaxLeasesPerBlock;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxListingsPerBlock = MaxListingsPerBlock;
+	type Assets = Assets;
+	type AssetFeeCollector = AssetFeeCollector;
+	type MutationChance = MutationChance;
+	type RevealDelay = RevealDelay;
+	type RevealWindow = RevealWindow;
+	type MaxCommitmentsPerBlock = MaxCommitmentsPerBlock;
+	type AllowInstantMint = AllowInstantMint;
+	type MaxOffersPerBlock = MaxOffersPerBlock;
+	type WeightInfo = ();
 }
 
 #[macro_export]
@@ -95,15 +176,31 @@ macro_rules! assert_has_event {
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with_kitties(vec![])
+}
+
+pub fn new_test_ext_with_kitties(kitties: Vec<(u64, [u8; 16])>) -> sp_io::TestExternalities {
 	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
 	pallet_balances::GenesisConfig::<Test> {
-		balances: vec![(1, 10_000_000_000), (2, 10_000_000_000), (3, 9_000)],
+		balances: vec![(1, 10_000_000_000), (2, 10_000_000_000), (3, 9_000), (4, 10_000_000_000)],
 	}
 		.assimilate_storage(&mut storage)
 		.unwrap();
 
+	pallet_kitties::GenesisConfig::<Test> { kitties }.assimilate_storage(&mut storage).unwrap();
+
 	let mut ext = sp_io::TestExternalities::new(storage);
 	ext.execute_with(|| System::set_block_number(1));
 	ext
-}
\ No newline at end of file
+}
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		SubstrateKitties::on_finalize(System::block_number());
+		System::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		System::on_initialize(System::block_number());
+		SubstrateKitties::on_initialize(System::block_number());
+	}
+}
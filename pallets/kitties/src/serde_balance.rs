@@ -0,0 +1,41 @@
+//! Serialize balances as decimal strings.
+//!
+//! JSON numbers cannot hold every `u128` and `serde_json` refuses to serialize them, so balances
+//! returned over RPC are written as strings, the same way `pallet-transaction-payment` does it.
+//! Use with `#[serde(with = "pallet_kitties::serde_balance")]`, or
+//! `#[serde(with = "pallet_kitties::serde_balance::option")]` for an `Option` of a balance.
+
+use serde::{Deserialize, Deserializer, Serializer};
+use std::{fmt::Display, str::FromStr};
+
+pub fn serialize<S: Serializer, T: Display>(balance: &T, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&balance.to_string())
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>, T: FromStr>(deserializer: D) -> Result<T, D::Error> {
+	let s = String::deserialize(deserializer)?;
+	s.parse::<T>().map_err(|_| serde::de::Error::custom("Parse from string failed"))
+}
+
+pub mod option {
+	use serde::{Deserialize, Deserializer, Serializer};
+	use std::{fmt::Display, str::FromStr};
+
+	pub fn serialize<S: Serializer, T: Display>(
+		balance: &Option<T>,
+		serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		match balance {
+			Some(balance) => serializer.serialize_some(&balance.to_string()),
+			None => serializer.serialize_none(),
+		}
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>, T: FromStr>(
+		deserializer: D,
+	) -> Result<Option<T>, D::Error> {
+		Option::<String>::deserialize(deserializer)?
+			.map(|s| s.parse::<T>().map_err(|_| serde::de::Error::custom("Parse from string failed")))
+			.transpose()
+	}
+}
@@ -1,8 +1,33 @@
 use super::*;
 use crate::mock::{
-    new_test_ext, Event as TestEvent, Origin, SubstrateKitties, System, Test,
+    new_test_ext, new_test_ext_with_kitties, run_to_block, AllowInstantMint, Assets, Balances, BreedCooldown, Event as TestEvent, FEE_ACCOUNT, GiftExpiry,
+    MaxAuctionsPerBlock, MaxCommitmentsPerBlock, MaxKittiesOwned, Origin, RevealDelay, RevealWindow, StakeForEachKitty, SubstrateKitties, System,
+    Test,
 };
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, GetStorageVersion, NamedReservableCurrency, ReservableCurrency, StorageVersion},
+};
+use sp_runtime::Permill;
+
+// 第i次繁殖使用的随机种子
+fn seed(i: u32) -> [u8; 16] {
+    sp_io::hashing::blake2_128(&i.to_le_bytes())
+}
+
+// 所有性状基因都相同的DNA
+fn dna_with_genes(gene: u8) -> [u8; 16] {
+    let mut dna = [0u8; 16];
+    for i in genes::TRAIT_GENES {
+        dna[i] = gene;
+    }
+    dna
+}
+
+// 设置Kitty的性别
+fn set_gender(kitty_id: u32, gender: Gender) {
+    Kitties::<Test>::mutate(kitty_id, |kitty| kitty.as_mut().unwrap().gender = gender);
+}
 
 #[test]
 fn create_works() {
@@ -15,6 +40,25 @@ fn create_works() {
         assert_eq!(Owner::<Test>::get(kitty_id), Some(account_id));
         // 检查创建成功事件
         assert_has_event!(Event::<Test>::KittyCreate(account_id, kitty_id));
+        // 检查账户的Kitty索引
+        assert_eq!(OwnedKitties::<Test>::get(account_id).into_inner(), vec![kitty_id]);
+        // 初代Kitty没有父母
+        let kitty = Kitties::<Test>::get(kitty_id).unwrap();
+        assert_eq!(kitty.parents, None);
+        assert_eq!(kitty.generation, 0);
+        assert_eq!(kitty.birth_block, 1);
+    });
+}
+
+#[test]
+fn create_kitty_failed_too_many_owned() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        for _ in 0..MaxKittiesOwned::get() {
+            assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        }
+        // 拥有的Kitty数量已达上限
+        assert_noop!(SubstrateKitties::create(Origin::signed(account_id)),Error::<Test>::TooManyOwned);
     });
 }
 
@@ -37,6 +81,38 @@ fn create_kitty_failed_not_enough_balance_for_staking() {
     });
 }
 
+#[test]
+fn genesis_config_works() {
+    let dna = dna_with_genes(0x21);
+    new_test_ext_with_kitties(vec![(1, dna), (2, dna_with_genes(0x03)), (1, dna_with_genes(0x44))]).execute_with(|| {
+        let stake = StakeForEachKitty::get();
+        assert_eq!(KittyCnt::<Test>::get(), Some(3));
+        assert_eq!(Kitties::<Test>::get(0).unwrap().dna, dna);
+        assert_eq!(Kitties::<Test>::get(0).unwrap().generation, 0);
+        assert_eq!(OwnedKitties::<Test>::get(1).into_inner(), vec![0, 2]);
+        assert_eq!(Owner::<Test>::get(1), Some(2));
+        // 从初始余额中质押
+        assert_eq!(Balances::reserved_balance_named(&KITTY_RESERVE_ID, &1), 2 * stake);
+        assert_eq!(Balances::reserved_balance_named(&KITTY_RESERVE_ID, &2), stake);
+        assert_eq!(TraitCounts::<Test>::get(genes::PATTERN as u8, 1), 1);
+        // 之后创建的Kitty接着创世区块的编号
+        assert_ok!(SubstrateKitties::create(Origin::signed(2)));
+        assert_eq!(Owner::<Test>::get(3), Some(2));
+    });
+}
+
+#[test]
+#[should_panic(expected = "NotEnoughBalanceForStaking")]
+fn genesis_config_failed_not_enough_balance_for_staking() {
+    new_test_ext_with_kitties(vec![(100, seed(0))]);
+}
+
+#[test]
+#[should_panic(expected = "TooManyOwned")]
+fn genesis_config_failed_too_many_owned() {
+    new_test_ext_with_kitties((0..=MaxKittiesOwned::get()).map(|i| (1, seed(i))).collect());
+}
+
 #[test]
 fn breed_works() {
     new_test_ext().execute_with(|| {
@@ -50,10 +126,62 @@ fn breed_works() {
         assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
         // 创建Kitty2
         assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        // 父母的性别不同
+        set_gender(kitty_id_1, Gender::Male);
+        set_gender(kitty_id_2, Gender::Female);
         // 繁殖
         assert_ok!(SubstrateKitties::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
         // 创建成功事件
         assert_has_event!(Event::<Test>::KittyCreate(account_id, kitty_id_3));
+        // 子代记录了父母和代数
+        let child = Kitties::<Test>::get(kitty_id_3).unwrap();
+        assert_eq!(child.parents, Some((kitty_id_1, kitty_id_2)));
+        assert_eq!(child.generation, 1);
+        assert_eq!(child.birth_block, 1);
+        assert_eq!(child.gender, Gender::from_dna(&child.dna));
+    });
+}
+
+#[test]
+fn breed_failed_kitty_on_cooldown() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        // parent kitty
+        let kitty_id_1 = 0u32;
+        let kitty_id_2 = 1u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        set_gender(kitty_id_1, Gender::Male);
+        set_gender(kitty_id_2, Gender::Female);
+        assert_ok!(SubstrateKitties::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
+        // 父母处于冷却期
+        assert_noop!(SubstrateKitties::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2),Error::<Test>::KittyOnCooldown);
+        // 冷却结束时通知所有者
+        run_to_block(1 + BreedCooldown::get());
+        assert_has_event!(Event::<Test>::KittyReady(account_id, kitty_id_1));
+        assert_has_event!(Event::<Test>::KittyReady(account_id, kitty_id_2));
+        assert_ok!(SubstrateKitties::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
+        // 第二次繁殖后冷却时间翻倍
+        assert_eq!(
+            BreedingStates::<Test>::get(kitty_id_1),
+            BreedingState { breed_count: 2, ready_at: 1 + 3 * BreedCooldown::get() }
+        );
+    });
+}
+
+#[test]
+fn breed_failed_same_gender() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        // parent kitty
+        let kitty_id_1 = 0u32;
+        let kitty_id_2 = 1u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        set_gender(kitty_id_1, Gender::Female);
+        set_gender(kitty_id_2, Gender::Female);
+        // 父母的性别相同
+        assert_noop!(SubstrateKitties::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2),Error::<Test>::SameGender);
     });
 }
 
@@ -94,6 +222,9 @@ fn breed_failed_count_overflow() {
         assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
         // 创建Kitty2
         assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        // 父母的性别不同
+        set_gender(kitty_id_1, Gender::Male);
+        set_gender(kitty_id_2, Gender::Female);
         // 繁殖时Kitty的ID已经达到最大值
         assert_noop!(SubstrateKitties::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2),Error::<Test>::KittiesCountOverflow);
     });
@@ -103,7 +234,6 @@ fn breed_failed_count_overflow() {
 fn breed_failed_not_enough_balance_for_staking() {
     new_test_ext().execute_with(|| {
         let account_id: u64 = 1;
-        let other_account_id: u64 = 100;
         // parent kitty
         let kitty_id_1 = 0u32;
         let kitty_id_2 = 1u32;
@@ -111,8 +241,75 @@ fn breed_failed_not_enough_balance_for_staking() {
         assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
         // 创建Kitty2
         assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        // 父母的性别不同
+        set_gender(kitty_id_1, Gender::Male);
+        set_gender(kitty_id_2, Gender::Female);
         // 繁殖但token不足
-        assert_noop!(SubstrateKitties::breed(Origin::signed(other_account_id), kitty_id_1, kitty_id_2),Error::<Test>::NotEnoughBalanceForStaking);
+        let _ = Balances::make_free_balance_be(&account_id, 1_000);
+        assert_noop!(SubstrateKitties::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2),Error::<Test>::NotEnoughBalanceForStaking);
+    });
+}
+
+#[test]
+fn breed_failed_not_owner() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        let other_account_id: u64 = 2;
+        // parent kitty
+        let kitty_id_1 = 0u32;
+        let kitty_id_2 = 1u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        set_gender(kitty_id_1, Gender::Male);
+        set_gender(kitty_id_2, Gender::Female);
+        // 不能使用他人的Kitty繁殖
+        assert_noop!(SubstrateKitties::breed(Origin::signed(other_account_id), kitty_id_1, kitty_id_2),Error::<Test>::NotOwner);
+    });
+}
+
+#[test]
+fn breed_with_sire_works() {
+    new_test_ext().execute_with(|| {
+        let account_id_1: u64 = 1;
+        let account_id_2: u64 = 2;
+        let sire_id = 0u32;
+        let kitty_id = 1u32;
+        let child_id = 2u32;
+        let fee = 500u128;
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id_1)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id_2)));
+        set_gender(sire_id, Gender::Male);
+        set_gender(kitty_id, Gender::Female);
+        // 出租种猫
+        assert_ok!(SubstrateKitties::offer_sire(Origin::signed(account_id_1), sire_id, Some(fee)));
+        assert_has_event!(Event::<Test>::SireOffered(account_id_1, sire_id, Some(fee)));
+        let sire_owner_balance = Balances::free_balance(&account_id_1);
+        // 支付配种费繁殖
+        assert_ok!(SubstrateKitties::breed_with_sire(Origin::signed(account_id_2), kitty_id, sire_id));
+        assert_has_event!(Event::<Test>::KittySired(account_id_2, account_id_1, sire_id, fee));
+        // 配种费转给种猫的所有者，子代属于调用者
+        assert_eq!(Balances::free_balance(&account_id_1), sire_owner_balance + fee);
+        assert_eq!(Owner::<Test>::get(child_id), Some(account_id_2));
+    });
+}
+
+#[test]
+fn breed_with_sire_failed_not_offered() {
+    new_test_ext().execute_with(|| {
+        let account_id_1: u64 = 1;
+        let account_id_2: u64 = 2;
+        let sire_id = 0u32;
+        let kitty_id = 1u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id_1)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id_2)));
+        set_gender(sire_id, Gender::Male);
+        set_gender(kitty_id, Gender::Female);
+        // 种猫未出租
+        assert_noop!(SubstrateKitties::breed_with_sire(Origin::signed(account_id_2), kitty_id, sire_id),Error::<Test>::SireNotOffered);
+        // 转让后取消出租
+        assert_ok!(SubstrateKitties::offer_sire(Origin::signed(account_id_1), sire_id, Some(500)));
+        assert_ok!(SubstrateKitties::transfer(Origin::signed(account_id_1), account_id_2, sire_id));
+        assert_eq!(SireListings::<Test>::get(sire_id), None);
     });
 }
 
@@ -155,6 +352,43 @@ fn transfer_works() {
         assert_ok!(SubstrateKitties::transfer(Origin::signed(account_id_1), account_id_2, kitty_id));
         // 转让事件
         assert_has_event!(Event::KittyTransfer(account_id_1, account_id_2, kitty_id));
+        // 双方的Kitty索引已更新
+        assert!(OwnedKitties::<Test>::get(account_id_1).is_empty());
+        assert_eq!(OwnedKitties::<Test>::get(account_id_2).into_inner(), vec![kitty_id]);
+    });
+}
+
+#[test]
+fn transfer_failed_too_many_owned() {
+    new_test_ext().execute_with(|| {
+        let account_id_1: u64 = 1;
+        let account_id_2: u64 = 2;
+        for _ in 0..MaxKittiesOwned::get() {
+            assert_ok!(SubstrateKitties::create(Origin::signed(account_id_2)));
+        }
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id_1)));
+        let kitty_id = MaxKittiesOwned::get();
+        // 接收方拥有的Kitty数量已达上限
+        assert_noop!(SubstrateKitties::transfer(Origin::signed(account_id_1), account_id_2, kitty_id),Error::<Test>::TooManyOwned);
+    });
+}
+
+#[test]
+fn transfer_keeps_other_reserves() {
+    new_test_ext().execute_with(|| {
+        let account_id_1: u64 = 1;
+        let account_id_2: u64 = 2;
+        let kitty_id = 0u32;
+        let stake = StakeForEachKitty::get();
+        // 其他模块的锁定
+        assert_ok!(Balances::reserve(&account_id_1, 5_000));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id_1)));
+        assert_eq!(Balances::reserved_balance_named(&KITTY_RESERVE_ID, &account_id_1), stake);
+        assert_eq!(KittyDeposits::<Test>::get(kitty_id), stake);
+        assert_ok!(SubstrateKitties::transfer(Origin::signed(account_id_1), account_id_2, kitty_id));
+        // 只释放Kitty的质押
+        assert_eq!(Balances::reserved_balance(&account_id_1), 5_000);
+        assert_eq!(Balances::reserved_balance_named(&KITTY_RESERVE_ID, &account_id_2), stake);
     });
 }
 
@@ -185,6 +419,58 @@ fn transfer_failed_not_enough_balance_for_staking() {
     });
 }
 
+#[test]
+fn transfer_from_works_with_approval() {
+    new_test_ext().execute_with(|| {
+        let owner: u64 = 1;
+        let approved: u64 = 2;
+        let receiver: u64 = 4;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(owner)));
+        // 未授权不能转移
+        assert_noop!(SubstrateKitties::transfer_from(Origin::signed(approved), owner, receiver, kitty_id),Error::<Test>::NotApproved);
+        assert_ok!(SubstrateKitties::approve(Origin::signed(owner), approved, kitty_id));
+        assert_has_event!(Event::<Test>::Approval(owner, approved, kitty_id));
+        assert_ok!(SubstrateKitties::transfer_from(Origin::signed(approved), owner, receiver, kitty_id));
+        assert_has_event!(Event::KittyTransfer(owner, receiver, kitty_id));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(receiver));
+        // 所有者变更后授权被清除
+        assert_eq!(Approvals::<Test>::get(kitty_id), None);
+    });
+}
+
+#[test]
+fn transfer_from_works_with_operator() {
+    new_test_ext().execute_with(|| {
+        let owner: u64 = 1;
+        let operator: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(owner)));
+        assert_ok!(SubstrateKitties::set_approval_for_all(Origin::signed(owner), operator, true));
+        assert_has_event!(Event::<Test>::ApprovalForAll(owner, operator, true));
+        assert_ok!(SubstrateKitties::transfer_from(Origin::signed(operator), owner, operator, kitty_id));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(operator));
+        // 取消操作者授权
+        assert_ok!(SubstrateKitties::create(Origin::signed(owner)));
+        assert_ok!(SubstrateKitties::set_approval_for_all(Origin::signed(owner), operator, false));
+        assert_noop!(SubstrateKitties::transfer_from(Origin::signed(operator), owner, operator, 1),Error::<Test>::NotApproved);
+    });
+}
+
+#[test]
+fn buy_clears_approval() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::approve(Origin::signed(1), 4, kitty_id));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(1), kitty_id, Some(1_000)));
+        assert_ok!(SubstrateKitties::buy(Origin::signed(2), kitty_id));
+        assert_eq!(Approvals::<Test>::get(kitty_id), None);
+        // 原来被授权的账户不能再转移
+        assert_noop!(SubstrateKitties::transfer_from(Origin::signed(4), 2, 4, kitty_id),Error::<Test>::NotApproved);
+    });
+}
+
 #[test]
 fn buy_works() {
     new_test_ext().execute_with(|| {
@@ -199,7 +485,33 @@ fn buy_works() {
         // 购买Kitty
         assert_ok!(SubstrateKitties::buy(Origin::signed(account_id_2), kitty_id));
         // 购买事件
-        assert_has_event!(Event::KittySold(account_id_2, account_id_1, kitty_id));
+        assert_has_event!(Event::KittySold(account_id_2, account_id_1, kitty_id, price, 100, 0));
+        // 买卖双方的Kitty索引已更新
+        assert!(OwnedKitties::<Test>::get(account_id_1).is_empty());
+        assert_eq!(OwnedKitties::<Test>::get(account_id_2).into_inner(), vec![kitty_id]);
+    });
+}
+
+#[test]
+fn resale_pays_fee_and_royalty() {
+    new_test_ext().execute_with(|| {
+        let creator: u64 = 1;
+        let seller: u64 = 2;
+        let buyer: u64 = 4;
+        let kitty_id = 0u32;
+        let price: u128 = 10_000;
+        assert_ok!(SubstrateKitties::create(Origin::signed(creator)));
+        assert_eq!(Creators::<Test>::get(kitty_id), Some(creator));
+        assert_ok!(SubstrateKitties::transfer(Origin::signed(creator), seller, kitty_id));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(seller), kitty_id, Some(price)));
+        let creator_balance = Balances::free_balance(&creator);
+        let seller_balance = Balances::free_balance(&seller);
+        assert_ok!(SubstrateKitties::buy(Origin::signed(buyer), kitty_id));
+        // 手续费10%，版税5%
+        assert_has_event!(Event::KittySold(buyer, seller, kitty_id, price, 1_000, 500));
+        assert_eq!(Balances::free_balance(&FEE_ACCOUNT), 1_000);
+        assert_eq!(Balances::free_balance(&creator), creator_balance + 500);
+        assert_eq!(Balances::free_balance(&seller), seller_balance + 8_500 + StakeForEachKitty::get());
     });
 }
 
@@ -245,4 +557,1198 @@ fn buy_failed_buyer_not_enough_balance_for_buying() {
         // 买家余额不够
         assert_noop!(SubstrateKitties::buy(Origin::signed(account_id_3), kitty_id),Error::<Test>::NotEnoughBalanceForBuying);
     });
-}
\ No newline at end of file
+}
+#[test]
+fn burn_works() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        let kitty_id_1 = 0u32;
+        let kitty_id_2 = 1u32;
+        let child_id = 2u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        set_gender(kitty_id_1, Gender::Male);
+        set_gender(kitty_id_2, Gender::Female);
+        assert_ok!(SubstrateKitties::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(account_id), kitty_id_1, Some(1_000)));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(2), kitty_id_1, 1_000, 10));
+        let reserved = Balances::reserved_balance(&account_id);
+        // 销毁Kitty
+        assert_ok!(SubstrateKitties::burn(Origin::signed(account_id), kitty_id_1));
+        assert_has_event!(Event::<Test>::KittyBurned(account_id, kitty_id_1));
+        assert_eq!(Kitties::<Test>::get(kitty_id_1), None);
+        assert_eq!(Owner::<Test>::get(kitty_id_1), None);
+        assert_eq!(ListForSale::<Test>::get(kitty_id_1), None);
+        assert_eq!(OwnedKitties::<Test>::get(account_id).into_inner(), vec![kitty_id_2, child_id]);
+        // 质押和报价被释放
+        assert_eq!(Balances::reserved_balance(&account_id), reserved - StakeForEachKitty::get());
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        // 子代不受影响
+        assert_eq!(Kitties::<Test>::get(child_id).unwrap().parents, Some((kitty_id_1, kitty_id_2)));
+        assert_eq!(Owner::<Test>::get(child_id), Some(account_id));
+    });
+}
+
+#[test]
+fn burn_failed_not_owner() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_noop!(SubstrateKitties::burn(Origin::signed(2), kitty_id),Error::<Test>::NotOwner);
+        // 拍卖中的Kitty不能销毁
+        assert_ok!(SubstrateKitties::create_auction(Origin::signed(1), kitty_id, 1_000, 5));
+        assert_noop!(SubstrateKitties::burn(Origin::signed(1), kitty_id),Error::<Test>::KittyInAuction);
+    });
+}
+
+#[test]
+fn buy_at_most_works() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let buyer: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(seller), kitty_id, Some(1_000)));
+        // 价格不超过买家可接受的最高价格
+        assert_ok!(SubstrateKitties::buy_at_most(Origin::signed(buyer), kitty_id, 1_000, None));
+        assert_has_event!(Event::<Test>::KittySold(buyer, seller, kitty_id, 1_000, 100, 0));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(buyer));
+    });
+}
+
+#[test]
+fn buy_at_most_failed_price_too_high() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let buyer: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(seller), kitty_id, Some(1_000)));
+        // 卖家在购买前抬价
+        assert_ok!(SubstrateKitties::sell(Origin::signed(seller), kitty_id, Some(5_000)));
+        assert_noop!(SubstrateKitties::buy_at_most(Origin::signed(buyer), kitty_id, 1_000, None),Error::<Test>::PriceTooHigh);
+    });
+}
+
+#[test]
+fn buy_at_most_failed_listing_asset_mismatch() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let buyer: u64 = 2;
+        let kitty_id = 0u32;
+        let asset_id = 1u32;
+        assert_ok!(Assets::force_create(Origin::root(), asset_id, seller, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(seller), asset_id, buyer, 10_000));
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(seller), kitty_id, Some(1_000)));
+        // 卖家在购买前把计价单位换成资产，价格数字不变
+        assert_ok!(SubstrateKitties::sell_for_asset(Origin::signed(seller), kitty_id, asset_id, 1_000));
+        assert_noop!(SubstrateKitties::buy_at_most(Origin::signed(buyer), kitty_id, 1_000, None),Error::<Test>::ListingAssetMismatch);
+        // 卖家再换回原生代币
+        assert_ok!(SubstrateKitties::sell(Origin::signed(seller), kitty_id, Some(1_000)));
+        assert_noop!(SubstrateKitties::buy_at_most(Origin::signed(buyer), kitty_id, 1_000, Some(asset_id)),Error::<Test>::ListingAssetMismatch);
+        // 计价资产一致时可以购买
+        assert_ok!(SubstrateKitties::buy_at_most(Origin::signed(buyer), kitty_id, 1_000, None));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(buyer));
+    });
+}
+
+#[test]
+fn sell_for_asset_failed_unknown_asset() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_noop!(SubstrateKitties::sell_for_asset(Origin::signed(1), 0, 1, 1_000),Error::<Test>::UnknownAsset);
+    });
+}
+
+#[test]
+fn sell_for_asset_failed_asset_price_too_low() {
+    new_test_ext().execute_with(|| {
+        let asset_id = 1u32;
+        assert_ok!(Assets::force_create(Origin::root(), asset_id, 1, true, 100));
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        // 版税低于资产的最小余额
+        assert_noop!(SubstrateKitties::sell_for_asset(Origin::signed(1), 0, asset_id, 1_000),Error::<Test>::AssetPriceTooLow);
+        assert_ok!(SubstrateKitties::sell_for_asset(Origin::signed(1), 0, asset_id, 2_000));
+    });
+}
+
+#[test]
+fn buy_with_asset_works() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let buyer: u64 = 2;
+        let kitty_id = 0u32;
+        let asset_id = 0u32;
+        let stake = StakeForEachKitty::get();
+        // 创建资产并发放给买家
+        assert_ok!(Assets::force_create(Origin::root(), asset_id, seller, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(seller), asset_id, buyer, 10_000));
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        assert_ok!(SubstrateKitties::sell_for_asset(Origin::signed(seller), kitty_id, asset_id, 1_000));
+        assert_has_event!(Event::<Test>::KittyListedForAsset(seller, kitty_id, asset_id, 1_000));
+        let buyer_balance = Balances::free_balance(&buyer);
+        assert_ok!(SubstrateKitties::buy(Origin::signed(buyer), kitty_id));
+        assert_has_event!(Event::<Test>::KittySoldForAsset(buyer, seller, kitty_id, asset_id, 1_000, 100, 0));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(buyer));
+        // 价款使用资产支付，手续费转给收款账户
+        assert_eq!(Assets::balance(asset_id, buyer), 9_000);
+        assert_eq!(Assets::balance(asset_id, seller), 900);
+        assert_eq!(Assets::balance(asset_id, FEE_ACCOUNT), 100);
+        // 质押仍使用原生代币
+        assert_eq!(Balances::free_balance(&buyer), buyer_balance - stake);
+        assert_eq!(Balances::reserved_balance(&buyer), stake);
+        assert_eq!(ListingAsset::<Test>::get(kitty_id), None);
+    });
+}
+
+#[test]
+fn buy_with_asset_failed_balance_low() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let buyer: u64 = 2;
+        let kitty_id = 0u32;
+        let asset_id = 0u32;
+        assert_ok!(Assets::force_create(Origin::root(), asset_id, seller, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(seller), asset_id, buyer, 500));
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        assert_ok!(SubstrateKitties::sell_for_asset(Origin::signed(seller), kitty_id, asset_id, 1_000));
+        // 买家的资产余额不足
+        assert_noop!(SubstrateKitties::buy(Origin::signed(buyer), kitty_id),pallet_assets::Error::<Test>::BalanceLow);
+    });
+}
+
+#[test]
+fn buy_dutch_listing_works() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let buyer: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        // 荷兰式拍卖，价格经过10个区块从10000降到1000
+        assert_ok!(SubstrateKitties::sell_dutch(Origin::signed(seller), kitty_id, 10_000, 1_000, 10));
+        assert_has_event!(Event::<Test>::KittyListedDutch(seller, kitty_id, 10_000, 1_000, 10));
+        assert_eq!(SubstrateKitties::sale_price(kitty_id), Some(10_000));
+        run_to_block(6);
+        assert_eq!(SubstrateKitties::sale_price(kitty_id), Some(5_500));
+        let seller_balance = Balances::free_balance(&seller);
+        // 买家按当前价格购买
+        assert_ok!(SubstrateKitties::buy(Origin::signed(buyer), kitty_id));
+        assert_has_event!(Event::KittySold(buyer, seller, kitty_id, 5_500, 550, 0));
+        assert_eq!(Balances::free_balance(&seller), seller_balance + 4_950 + StakeForEachKitty::get());
+        assert_eq!(DutchListings::<Test>::get(kitty_id), None);
+    });
+}
+
+#[test]
+fn dutch_listing_price_stops_at_end_price() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::sell_dutch(Origin::signed(1), kitty_id, 10_000, 1_000, 10));
+        run_to_block(20);
+        assert_eq!(SubstrateKitties::sale_price(kitty_id), Some(1_000));
+    });
+}
+
+#[test]
+fn sell_dutch_failed_invalid_dutch_auction() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        // 起始价格低于最终价格
+        assert_noop!(SubstrateKitties::sell_dutch(Origin::signed(1), kitty_id, 1_000, 10_000, 10),Error::<Test>::InvalidDutchAuction);
+        // 降价时长为0
+        assert_noop!(SubstrateKitties::sell_dutch(Origin::signed(1), kitty_id, 10_000, 1_000, 0),Error::<Test>::InvalidDutchAuction);
+    });
+}
+
+#[test]
+fn accept_offer_works() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let buyer_1: u64 = 2;
+        let buyer_2: u64 = 4;
+        let kitty_id = 0u32;
+        let stake = StakeForEachKitty::get();
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        // 两个买家报价，报价金额被锁定
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(buyer_1), kitty_id, 1_000, 10));
+        assert_has_event!(Event::<Test>::OfferMade(buyer_1, kitty_id, 1_000, 10));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(buyer_2), kitty_id, 2_000, 10));
+        assert_eq!(Balances::reserved_balance(&buyer_1), 1_000);
+        let seller_balance = Balances::free_balance(&seller);
+        // 卖家接受第一个报价
+        assert_ok!(SubstrateKitties::accept_offer(Origin::signed(seller), kitty_id, buyer_1));
+        assert_has_event!(Event::KittySold(buyer_1, seller, kitty_id, 1_000, 100, 0));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(buyer_1));
+        assert_eq!(Balances::free_balance(&seller), seller_balance + 900 + stake);
+        assert_eq!(Balances::reserved_balance(&buyer_1), stake);
+        // 其余报价被释放
+        assert_eq!(Balances::reserved_balance(&buyer_2), 0);
+        assert!(Offers::<Test>::get(kitty_id).is_empty());
+    });
+}
+
+#[test]
+fn withdraw_offer_works() {
+    new_test_ext().execute_with(|| {
+        let buyer: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(buyer), kitty_id, 1_000, 10));
+        assert_ok!(SubstrateKitties::withdraw_offer(Origin::signed(buyer), kitty_id));
+        assert_has_event!(Event::<Test>::OfferWithdrawn(buyer, kitty_id));
+        assert_eq!(Balances::reserved_balance(&buyer), 0);
+        // 报价不存在
+        assert_noop!(SubstrateKitties::withdraw_offer(Origin::signed(buyer), kitty_id),Error::<Test>::OfferNotFound);
+    });
+}
+
+#[test]
+fn accept_offer_failed_offer_expired() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let buyer: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(buyer), kitty_id, 1_000, 5));
+        run_to_block(5);
+        // 报价已过期
+        assert_noop!(SubstrateKitties::accept_offer(Origin::signed(seller), kitty_id, buyer),Error::<Test>::OfferExpired);
+    });
+}
+
+#[test]
+fn make_offer_failed_too_many_offers() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(2), kitty_id, 1_000, 5));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(3), kitty_id, 1_000, 10));
+        // 报价数量已达上限
+        assert_noop!(SubstrateKitties::make_offer(Origin::signed(4), kitty_id, 1_000, 10),Error::<Test>::TooManyOffers);
+        // 过期的报价被清理后可以继续报价
+        run_to_block(5);
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(4), kitty_id, 1_000, 10));
+        assert_eq!(Balances::reserved_balance(&2), 0);
+    });
+}
+
+#[test]
+fn offer_expires_and_unreserves() {
+    new_test_ext().execute_with(|| {
+        let buyer: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(buyer), kitty_id, 1_000, 5));
+        assert_eq!(Balances::reserved_balance(&buyer), 1_000);
+        // 过期的报价在失效区块自动清理，不需要再次报价
+        run_to_block(5);
+        assert_has_event!(Event::<Test>::OfferExpired(buyer, kitty_id));
+        assert_eq!(Balances::reserved_balance(&buyer), 0);
+        assert!(Offers::<Test>::get(kitty_id).is_empty());
+    });
+}
+
+#[test]
+fn make_offer_failed_too_many_offers_expiring() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(2), 0, 1_000, 10));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(2), 1, 1_000, 10));
+        assert_noop!(SubstrateKitties::make_offer(Origin::signed(4), 0, 1_000, 10),Error::<Test>::TooManyOffersExpiring);
+        // 撤回的报价从失效队列中移除
+        assert_ok!(SubstrateKitties::withdraw_offer(Origin::signed(2), 1));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(4), 0, 1_000, 10));
+    });
+}
+
+#[test]
+fn make_offer_outbids_lowest_offer() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(2), kitty_id, 1, 10));
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(3), kitty_id, 1_000, 10));
+        // 不高于最低报价时无法挤出
+        assert_noop!(SubstrateKitties::make_offer(Origin::signed(4), kitty_id, 1, 10),Error::<Test>::TooManyOffers);
+        // 更高的报价挤出最低报价，被挤出的金额被释放
+        assert_ok!(SubstrateKitties::make_offer(Origin::signed(4), kitty_id, 2, 10));
+        assert_has_event!(Event::<Test>::OfferOutbid(2, kitty_id));
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::reserved_balance(&4), 2);
+        let buyers: Vec<u64> = SubstrateKitties::offers(kitty_id).iter().map(|offer| offer.buyer).collect();
+        assert_eq!(buyers, vec![3, 4]);
+    });
+}
+
+#[test]
+fn auction_works() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let bidder_1: u64 = 2;
+        let bidder_2: u64 = 4;
+        let kitty_id = 0u32;
+        let end = 5u64;
+        let stake = StakeForEachKitty::get();
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        // 发起拍卖
+        assert_ok!(SubstrateKitties::create_auction(Origin::signed(seller), kitty_id, 1000, end));
+        assert_has_event!(Event::<Test>::AuctionCreated(seller, kitty_id, 1000, end));
+        // 拍卖期间不能转让
+        assert_noop!(SubstrateKitties::transfer(Origin::signed(seller), bidder_1, kitty_id),Error::<Test>::KittyInAuction);
+        // 出价
+        assert_ok!(SubstrateKitties::bid(Origin::signed(bidder_1), kitty_id, 1000));
+        assert_eq!(Balances::reserved_balance(&bidder_1), 1000 + stake);
+        // 出价被超过后释放锁定的金额
+        assert_ok!(SubstrateKitties::bid(Origin::signed(bidder_2), kitty_id, 2000));
+        assert_has_event!(Event::<Test>::BidPlaced(bidder_2, kitty_id, 2000));
+        assert_eq!(Balances::reserved_balance(&bidder_1), 0);
+        let seller_balance = Balances::free_balance(&seller);
+        // 到期自动结算
+        run_to_block(end);
+        assert_has_event!(Event::<Test>::AuctionSettled(bidder_2, seller, kitty_id, 2000));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(bidder_2));
+        assert_eq!(Auctions::<Test>::get(kitty_id), None);
+        assert_has_event!(Event::<Test>::KittySold(bidder_2, seller, kitty_id, 2000, 200, 0));
+        assert_eq!(Balances::free_balance(&seller), seller_balance + 1800 + stake);
+        assert_eq!(Balances::reserved_balance(&seller), 0);
+        assert_eq!(Balances::reserved_balance(&bidder_2), stake);
+    });
+}
+
+#[test]
+fn auction_without_bids_is_cancelled() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        assert_ok!(SubstrateKitties::create_auction(Origin::signed(seller), kitty_id, 1000, 5));
+        run_to_block(5);
+        // 没有出价时拍卖取消，Kitty仍属于卖家
+        assert_has_event!(Event::<Test>::AuctionCancelled(seller, kitty_id));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(seller));
+        assert_eq!(Auctions::<Test>::get(kitty_id), None);
+    });
+}
+
+#[test]
+fn bid_failed_bid_too_low() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        assert_ok!(SubstrateKitties::create_auction(Origin::signed(seller), kitty_id, 1000, 5));
+        // 出价低于保留价
+        assert_noop!(SubstrateKitties::bid(Origin::signed(2), kitty_id, 999),Error::<Test>::BidTooLow);
+        assert_ok!(SubstrateKitties::bid(Origin::signed(2), kitty_id, 1000));
+        // 出价没有超过当前最高出价
+        assert_noop!(SubstrateKitties::bid(Origin::signed(4), kitty_id, 1000),Error::<Test>::BidTooLow);
+    });
+}
+
+#[test]
+fn cancel_auction_failed_has_bids() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        assert_ok!(SubstrateKitties::create_auction(Origin::signed(seller), kitty_id, 1000, 5));
+        assert_ok!(SubstrateKitties::bid(Origin::signed(2), kitty_id, 1000));
+        // 已有出价的拍卖不能取消
+        assert_noop!(SubstrateKitties::cancel_auction(Origin::signed(seller), kitty_id),Error::<Test>::AuctionHasBids);
+    });
+}
+
+#[test]
+fn create_auction_failed_too_many_auctions_ending() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        for kitty_id in 0..=MaxAuctionsPerBlock::get() {
+            assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+            if kitty_id < MaxAuctionsPerBlock::get() {
+                assert_ok!(SubstrateKitties::create_auction(Origin::signed(seller), kitty_id, 1000, 5));
+            }
+        }
+        // 同一区块结算的拍卖数量已达上限
+        let kitty_id = MaxAuctionsPerBlock::get();
+        assert_noop!(SubstrateKitties::create_auction(Origin::signed(seller), kitty_id, 1000, 5),Error::<Test>::TooManyAuctionsEnding);
+    });
+}
+
+#[test]
+fn gift_works() {
+    new_test_ext().execute_with(|| {
+        let sender: u64 = 1;
+        let recipient: u64 = 2;
+        let kitty_id = 0u32;
+        let stake = StakeForEachKitty::get();
+        let expires = 1 + GiftExpiry::get();
+        assert_ok!(SubstrateKitties::create(Origin::signed(sender)));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(sender), kitty_id, Some(1_000)));
+        // 发起赠送，Kitty不再出售
+        assert_ok!(SubstrateKitties::gift(Origin::signed(sender), recipient, kitty_id));
+        assert_has_event!(Event::<Test>::GiftOffered(sender, recipient, kitty_id, expires));
+        assert_eq!(ListForSale::<Test>::get(kitty_id), None);
+        // 确认前不需要接收方质押
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(sender));
+        assert_eq!(Balances::reserved_balance(&recipient), 0);
+        // 只有接收方可以确认
+        assert_noop!(SubstrateKitties::accept_gift(Origin::signed(4), kitty_id),Error::<Test>::NotGiftRecipient);
+        assert_ok!(SubstrateKitties::accept_gift(Origin::signed(recipient), kitty_id));
+        assert_has_event!(Event::<Test>::GiftAccepted(sender, recipient, kitty_id));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(recipient));
+        assert_eq!(Balances::reserved_balance(&sender), 0);
+        assert_eq!(Balances::reserved_balance(&recipient), stake);
+        assert_eq!(PendingGifts::<Test>::get(kitty_id), None);
+        assert!(GiftsExpiringAt::<Test>::get(expires).is_empty());
+    });
+}
+
+#[test]
+fn reject_gift_works() {
+    new_test_ext().execute_with(|| {
+        let sender: u64 = 1;
+        let recipient: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(sender)));
+        assert_ok!(SubstrateKitties::gift(Origin::signed(sender), recipient, kitty_id));
+        // 接收方拒绝赠送，Kitty仍属于赠送方且不再锁定
+        assert_ok!(SubstrateKitties::reject_gift(Origin::signed(recipient), kitty_id));
+        assert_has_event!(Event::<Test>::GiftRejected(sender, recipient, kitty_id));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(sender));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(sender), kitty_id, Some(1_000)));
+        assert_noop!(SubstrateKitties::accept_gift(Origin::signed(recipient), kitty_id),Error::<Test>::GiftNotFound);
+    });
+}
+
+#[test]
+fn gift_expires() {
+    new_test_ext().execute_with(|| {
+        let sender: u64 = 1;
+        let recipient: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(sender)));
+        assert_ok!(SubstrateKitties::gift(Origin::signed(sender), recipient, kitty_id));
+        run_to_block(1 + GiftExpiry::get());
+        // 超时未确认的赠送自动取消
+        assert_has_event!(Event::<Test>::GiftExpired(sender, recipient, kitty_id));
+        assert_eq!(PendingGifts::<Test>::get(kitty_id), None);
+        assert_noop!(SubstrateKitties::accept_gift(Origin::signed(recipient), kitty_id),Error::<Test>::GiftNotFound);
+    });
+}
+
+#[test]
+fn gift_failed_kitty_gift_pending() {
+    new_test_ext().execute_with(|| {
+        let sender: u64 = 1;
+        assert_ok!(SubstrateKitties::create(Origin::signed(sender)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(sender)));
+        set_gender(0, Gender::Male);
+        set_gender(1, Gender::Female);
+        assert_ok!(SubstrateKitties::gift(Origin::signed(sender), 2, 0));
+        // 赠送期间不能出售、转让和繁殖
+        assert_noop!(SubstrateKitties::sell(Origin::signed(sender), 0, Some(1_000)),Error::<Test>::KittyGiftPending);
+        assert_noop!(SubstrateKitties::transfer(Origin::signed(sender), 4, 0),Error::<Test>::KittyGiftPending);
+        assert_noop!(SubstrateKitties::breed(Origin::signed(sender), 0, 1),Error::<Test>::KittyGiftPending);
+        assert_noop!(SubstrateKitties::gift(Origin::signed(sender), 4, 0),Error::<Test>::KittyGiftPending);
+    });
+}
+
+#[test]
+fn rent_works() {
+    new_test_ext().execute_with(|| {
+        let owner: u64 = 1;
+        let renter: u64 = 2;
+        let kitty_id = 0u32;
+        let renter_kitty_id = 1u32;
+        let child_id = 2u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(owner)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(renter)));
+        set_gender(kitty_id, Gender::Male);
+        set_gender(renter_kitty_id, Gender::Female);
+        // 出租Kitty，每个区块100，最长10个区块
+        assert_ok!(SubstrateKitties::list_for_rent(Origin::signed(owner), kitty_id, 100, 10));
+        assert_has_event!(Event::<Test>::KittyListedForRent(owner, kitty_id, 100, 10));
+        let owner_balance = Balances::free_balance(&owner);
+        // 租用5个区块，预付租金
+        assert_ok!(SubstrateKitties::rent(Origin::signed(renter), kitty_id, 5));
+        assert_has_event!(Event::<Test>::KittyRented(renter, owner, kitty_id, 6, 500));
+        assert_eq!(Balances::free_balance(&owner), owner_balance + 500);
+        // 所有权不变，使用权归租用者
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(owner));
+        assert_eq!(SubstrateKitties::user_of(kitty_id), Some(renter));
+        // 租用者可以使用Kitty繁殖，子代属于租用者
+        assert_ok!(SubstrateKitties::breed(Origin::signed(renter), kitty_id, renter_kitty_id));
+        assert_eq!(Owner::<Test>::get(child_id), Some(renter));
+        // 租期内所有者不能出售和转让
+        assert_noop!(SubstrateKitties::sell(Origin::signed(owner), kitty_id, Some(1_000)),Error::<Test>::KittyLeased);
+        assert_noop!(SubstrateKitties::transfer(Origin::signed(owner), 4, kitty_id),Error::<Test>::KittyLeased);
+        // 租用者不能转让
+        assert_noop!(SubstrateKitties::transfer(Origin::signed(renter), 4, kitty_id),Error::<Test>::NotOwner);
+        // 租期结束自动归还
+        run_to_block(6);
+        assert_has_event!(Event::<Test>::LeaseEnded(renter, kitty_id));
+        assert_eq!(Leases::<Test>::get(kitty_id), None);
+        assert_eq!(SubstrateKitties::user_of(kitty_id), Some(owner));
+        assert_ok!(SubstrateKitties::transfer(Origin::signed(owner), 4, kitty_id));
+    });
+}
+
+#[test]
+fn rent_failed_invalid_lease_duration() {
+    new_test_ext().execute_with(|| {
+        let owner: u64 = 1;
+        let renter: u64 = 2;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(owner)));
+        // 未出租
+        assert_noop!(SubstrateKitties::rent(Origin::signed(renter), kitty_id, 5),Error::<Test>::KittyNotForRent);
+        assert_ok!(SubstrateKitties::list_for_rent(Origin::signed(owner), kitty_id, 100, 10));
+        // 租期超过上限或为0
+        assert_noop!(SubstrateKitties::rent(Origin::signed(renter), kitty_id, 11),Error::<Test>::InvalidLeaseDuration);
+        assert_noop!(SubstrateKitties::rent(Origin::signed(renter), kitty_id, 0),Error::<Test>::InvalidLeaseDuration);
+        assert_ok!(SubstrateKitties::rent(Origin::signed(renter), kitty_id, 10));
+        // 已经出租的Kitty不能重复租用
+        assert_noop!(SubstrateKitties::rent(Origin::signed(4), kitty_id, 5),Error::<Test>::KittyLeased);
+    });
+}
+
+#[test]
+fn create_batch_works() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        assert_ok!(SubstrateKitties::create_batch(Origin::signed(account_id), 3));
+        assert_has_event!(Event::<Test>::BatchCompleted(account_id, 3));
+        assert_eq!(OwnedKitties::<Test>::get(account_id).into_inner(), vec![0, 1, 2]);
+        // 同一批次的Kitty的DNA不同
+        assert_ne!(Kitties::<Test>::get(0).unwrap().dna, Kitties::<Test>::get(1).unwrap().dna);
+        // 超过批量上限
+        assert_noop!(SubstrateKitties::create_batch(Origin::signed(account_id), 4),Error::<Test>::BatchTooLarge);
+        // 超过拥有上限时全部回滚
+        assert_noop!(SubstrateKitties::create_batch(Origin::signed(account_id), 3),Error::<Test>::TooManyOwned);
+    });
+}
+
+#[test]
+fn transfer_batch_works() {
+    new_test_ext().execute_with(|| {
+        let sender: u64 = 1;
+        let receiver: u64 = 2;
+        let stake = StakeForEachKitty::get();
+        assert_ok!(SubstrateKitties::create_batch(Origin::signed(sender), 3));
+        let kitty_ids: BoundedVec<u32, _> = vec![0, 2].try_into().unwrap();
+        assert_ok!(SubstrateKitties::transfer_batch(Origin::signed(sender), receiver, kitty_ids));
+        assert_has_event!(Event::<Test>::KittyTransfer(sender, receiver, 0));
+        assert_has_event!(Event::<Test>::KittyTransfer(sender, receiver, 2));
+        assert_has_event!(Event::<Test>::BatchCompleted(sender, 2));
+        assert_eq!(OwnedKitties::<Test>::get(receiver).into_inner(), vec![0, 2]);
+        assert_eq!(Balances::reserved_balance(&receiver), 2 * stake);
+    });
+}
+
+#[test]
+fn transfer_batch_failed_not_owner() {
+    new_test_ext().execute_with(|| {
+        let sender: u64 = 1;
+        assert_ok!(SubstrateKitties::create(Origin::signed(sender)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(2)));
+        // 其中一个Kitty不属于调用者时全部回滚
+        let kitty_ids: BoundedVec<u32, _> = vec![0, 1].try_into().unwrap();
+        assert_noop!(SubstrateKitties::transfer_batch(Origin::signed(sender), 4, kitty_ids),Error::<Test>::NotOwner);
+        assert_eq!(Owner::<Test>::get(0), Some(sender));
+    });
+}
+
+#[test]
+fn sell_batch_works() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        assert_ok!(SubstrateKitties::create_batch(Origin::signed(seller), 2));
+        let listings: BoundedVec<(u32, Option<u128>), _> = vec![(0, Some(1_000)), (1, Some(2_000))].try_into().unwrap();
+        assert_ok!(SubstrateKitties::sell_batch(Origin::signed(seller), listings));
+        assert_has_event!(Event::<Test>::KittyListed(seller, 1, Some(2_000)));
+        assert_has_event!(Event::<Test>::BatchCompleted(seller, 2));
+        assert_eq!(ListForSale::<Test>::get(0), Some(1_000));
+        assert_eq!(ListForSale::<Test>::get(1), Some(2_000));
+    });
+}
+
+#[test]
+fn sell_with_expiry_works() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        // 失效区块必须在未来
+        assert_noop!(SubstrateKitties::sell_with_expiry(Origin::signed(seller), kitty_id, 1_000, 1),Error::<Test>::InvalidListingExpiry);
+        assert_ok!(SubstrateKitties::sell_with_expiry(Origin::signed(seller), kitty_id, 1_000, 5));
+        assert_eq!(ListingExpiry::<Test>::get(kitty_id), Some(5));
+        // 到期后自动下架
+        run_to_block(5);
+        assert_has_event!(Event::<Test>::ListingExpired(kitty_id));
+        assert_eq!(ListForSale::<Test>::get(kitty_id), None);
+        assert_eq!(ListingExpiry::<Test>::get(kitty_id), None);
+        assert_noop!(SubstrateKitties::buy(Origin::signed(2), kitty_id),Error::<Test>::KittyNotForSell);
+    });
+}
+
+#[test]
+fn cancel_listing_works() {
+    new_test_ext().execute_with(|| {
+        let seller: u64 = 1;
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(seller)));
+        // 没有挂单
+        assert_noop!(SubstrateKitties::cancel_listing(Origin::signed(seller), kitty_id),Error::<Test>::KittyNotForSell);
+        assert_ok!(SubstrateKitties::sell_with_expiry(Origin::signed(seller), kitty_id, 1_000, 5));
+        assert_ok!(SubstrateKitties::cancel_listing(Origin::signed(seller), kitty_id));
+        assert_has_event!(Event::<Test>::ListingCancelled(seller, kitty_id));
+        assert_eq!(ListForSale::<Test>::get(kitty_id), None);
+        // 挂单从失效队列中移除
+        assert!(ListingsExpiringAt::<Test>::get(5).is_empty());
+    });
+}
+
+#[test]
+fn transfer_clears_listing() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(1), kitty_id, Some(1_000)));
+        assert_ok!(SubstrateKitties::transfer(Origin::signed(1), 2, kitty_id));
+        // 新的所有者没有挂单，不能按旧的价格购买
+        assert_eq!(ListForSale::<Test>::get(kitty_id), None);
+        assert_noop!(SubstrateKitties::buy(Origin::signed(4), kitty_id),Error::<Test>::KittyNotForSell);
+    });
+}
+
+#[test]
+fn kitty_traits_works() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        // 低4位是显性等位基因，高4位是隐性等位基因
+        let mut dna = [0u8; 16];
+        dna[0] = 1;
+        dna[genes::BODY_COLOUR] = 0x72;
+        dna[genes::PATTERN] = 0x0a;
+        dna[genes::RARITY] = 0x0f;
+        Kitties::<Test>::mutate(kitty_id, |kitty| kitty.as_mut().unwrap().dna = dna);
+        let traits = SubstrateKitties::kitty_traits(kitty_id).unwrap();
+        assert_eq!(traits.gender, Gender::Female);
+        assert_eq!(traits.body_colour, genes::Trait { value: genes::BodyColour::Cottoncandy, mutated: false });
+        // 等位基因10是Tigerpatch的变异形态
+        assert_eq!(traits.pattern, genes::Trait { value: genes::Pattern::Tigerpatch, mutated: true });
+        assert_eq!(traits.eye_shape.value, genes::EyeShape::Thicccbrowz);
+        assert_eq!(traits.rarity, genes::RarityTier::Legendary);
+        // 不存在的Kitty
+        assert_eq!(SubstrateKitties::kitty_traits(1), None);
+    });
+}
+
+#[test]
+fn create_has_only_base_traits() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(SubstrateKitties::create_batch(Origin::signed(1), 3));
+        // 初代Kitty没有变异形态
+        for kitty_id in 0..3 {
+            let traits = SubstrateKitties::kitty_traits(kitty_id).unwrap();
+            assert!(!traits.body_colour.mutated && !traits.pattern.mutated && !traits.mouth.mutated);
+            assert_eq!(traits.rarity, genes::RarityTier::Common);
+        }
+    });
+}
+
+#[test]
+fn mix_is_deterministic() {
+    let (dna_1, dna_2) = (seed(1), seed(2));
+    let chance = Permill::from_percent(50);
+    assert_eq!(genes::mix(&dna_1, &dna_2, &seed(3), chance), genes::mix(&dna_1, &dna_2, &seed(3), chance));
+    assert_ne!(genes::mix(&dna_1, &dna_2, &seed(3), chance), genes::mix(&dna_1, &dna_2, &seed(4), chance));
+}
+
+#[test]
+fn mix_only_inherits_parent_alleles() {
+    let (dna_1, dna_2) = (genes::base_dna(seed(1)), genes::base_dna(seed(2)));
+    for i in 0..1000 {
+        let child = genes::mix(&dna_1, &dna_2, &seed(i), Permill::zero());
+        for gene in genes::TRAIT_GENES {
+            let alleles = [
+                genes::dominant(&dna_1, gene),
+                genes::recessive(&dna_1, gene),
+                genes::dominant(&dna_2, gene),
+                genes::recessive(&dna_2, gene),
+            ];
+            // 没有变异时子代的等位基因都来自父母，且较小的等位基因是显性的
+            assert!(alleles.contains(&genes::dominant(&child, gene)));
+            assert!(alleles.contains(&genes::recessive(&child, gene)));
+            assert!(genes::dominant(&child, gene) <= genes::recessive(&child, gene));
+        }
+    }
+}
+
+#[test]
+fn mix_recessive_trait_distribution() {
+    // 父母的显性等位基因都是2，隐性等位基因都是5
+    let dna = dna_with_genes(0x52);
+    let mut recessive = 0;
+    for i in 0..1000 {
+        let child = genes::mix(&dna, &dna, &seed(i), Permill::zero());
+        match genes::dominant(&child, genes::BODY_COLOUR) {
+            5 => recessive += 1,
+            allele => assert_eq!(allele, 2),
+        }
+    }
+    // 父母都遗传隐性等位基因时才表现隐性性状，概率为1/16
+    assert!(recessive > 30 && recessive < 100, "recessive = {}", recessive);
+}
+
+#[test]
+fn mix_gender_distribution() {
+    let (dna_1, dna_2) = (seed(1), seed(2));
+    let females = (0..1000)
+        .filter(|i| Gender::from_dna(&genes::mix(&dna_1, &dna_2, &seed(*i), Permill::zero())) == Gender::Female)
+        .count();
+    assert!(females > 400 && females < 600, "females = {}", females);
+}
+
+#[test]
+fn mix_mutation_distribution() {
+    // 父母分别只有等位基因0和1，遗传的等位基因总是一对
+    let (dna_1, dna_2) = (dna_with_genes(0x00), dna_with_genes(0x11));
+    let mutations = |chance: Permill| {
+        (0..1000)
+            .filter(|i| genes::dominant(&genes::mix(&dna_1, &dna_2, &seed(*i), chance), genes::PATTERN) == 8)
+            .count()
+    };
+    assert_eq!(mutations(Permill::zero()), 0);
+    assert_eq!(mutations(Permill::one()), 1000);
+    let count = mutations(Permill::from_percent(25));
+    assert!(count > 180 && count < 320, "mutations = {}", count);
+}
+
+#[test]
+fn mix_mutation_requires_allele_pair() {
+    // 等位基因0和2不是一对，不会变异
+    let (dna_1, dna_2) = (dna_with_genes(0x00), dna_with_genes(0x22));
+    for i in 0..100 {
+        let child = genes::mix(&dna_1, &dna_2, &seed(i), Permill::one());
+        assert_eq!(genes::dominant(&child, genes::PATTERN), 0);
+    }
+    // 变异形态8和9组成一对时可以继续变异为稀有性状
+    let (dna_1, dna_2) = (dna_with_genes(0x88), dna_with_genes(0x99));
+    let child = genes::mix(&dna_1, &dna_2, &seed(0), Permill::one());
+    assert_eq!(genes::KittyTraits::from_dna(&child).rarity, genes::RarityTier::Rare);
+}
+
+#[test]
+fn trait_counts_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(SubstrateKitties::create_batch(Origin::signed(1), 3));
+        // 每个基因的统计数量之和等于现存的Kitty数量
+        for gene in genes::TRAIT_GENES {
+            assert_eq!(SubstrateKitties::trait_counts(gene as u8).iter().sum::<u32>(), 3);
+        }
+        let kitty = Kitties::<Test>::get(0).unwrap();
+        let allele = genes::dominant(&kitty.dna, genes::BODY_COLOUR);
+        let count = TraitCounts::<Test>::get(genes::BODY_COLOUR as u8, allele);
+        // 销毁后统计数量减少
+        assert_ok!(SubstrateKitties::burn(Origin::signed(1), 0));
+        assert_eq!(TraitCounts::<Test>::get(genes::BODY_COLOUR as u8, allele), count - 1);
+        assert_eq!(SubstrateKitties::trait_counts(genes::BODY_COLOUR as u8).iter().sum::<u32>(), 2);
+    });
+}
+
+#[test]
+fn rarity_score_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(SubstrateKitties::create_batch(Origin::signed(1), 3));
+        // 所有Kitty的性状相同时每个性状的评分为100
+        let mut common = [0u8; 16];
+        common[genes::PATTERN] = 1;
+        let mut rare = common;
+        rare[genes::PATTERN] = 2;
+        for (kitty_id, dna) in [(0u32, common), (1, common), (2, rare)] {
+            let old = Kitties::<Test>::get(kitty_id).unwrap().dna;
+            SubstrateKitties::count_traits(&old, false);
+            Kitties::<Test>::mutate(kitty_id, |kitty| kitty.as_mut().unwrap().dna = dna);
+            SubstrateKitties::count_traits(&dna, true);
+        }
+        let genes = genes::TRAIT_GENES.len() as u32;
+        // 图案的占比分别为2/3和1/3
+        assert_eq!(SubstrateKitties::rarity_score(0), Some((genes - 1) * 100 + 150));
+        assert_eq!(SubstrateKitties::rarity_score(2), Some((genes - 1) * 100 + 300));
+        assert_eq!(SubstrateKitties::rarity_score(3), None);
+    });
+}
+
+#[test]
+fn commit_create_works() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        let commitment_id = 0u64;
+        let stake = StakeForEachKitty::get();
+        assert_ok!(SubstrateKitties::commit_create(Origin::signed(account_id)));
+        let reveal_at = 1 + RevealDelay::get();
+        assert_has_event!(Event::<Test>::MintCommitted(account_id, commitment_id, reveal_at));
+        // 承诺时锁定质押
+        assert_eq!(Balances::reserved_balance_named(&KITTY_RESERVE_ID, &account_id), stake);
+        // 使用的区块哈希产生之前不能揭示
+        run_to_block(reveal_at);
+        assert_noop!(SubstrateKitties::reveal(Origin::signed(2), commitment_id),Error::<Test>::RevealTooEarly);
+        assert!(Commitments::<Test>::get(commitment_id).is_some());
+        assert_eq!(Owner::<Test>::get(0), None);
+        // 下一个区块自动揭示，Kitty归承诺者所有
+        run_to_block(reveal_at + 1);
+        assert_has_event!(Event::<Test>::KittyCreate(account_id, 0));
+        assert_has_event!(Event::<Test>::CommitmentRevealed(account_id, commitment_id));
+        assert_eq!(Owner::<Test>::get(0), Some(account_id));
+        assert_eq!(Balances::reserved_balance_named(&KITTY_RESERVE_ID, &account_id), stake);
+        assert_eq!(Commitments::<Test>::get(commitment_id), None);
+        assert_noop!(SubstrateKitties::reveal(Origin::signed(2), commitment_id),Error::<Test>::CommitmentNotFound);
+    });
+}
+
+#[test]
+fn commit_breed_works() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        set_gender(0, Gender::Male);
+        set_gender(1, Gender::Female);
+        assert_ok!(SubstrateKitties::commit_breed(Origin::signed(account_id), 0, 1));
+        // 承诺后父母立即进入冷却期
+        assert_noop!(SubstrateKitties::commit_breed(Origin::signed(account_id), 0, 1),Error::<Test>::KittyOnCooldown);
+        run_to_block(2 + RevealDelay::get());
+        assert_has_event!(Event::<Test>::CommitmentRevealed(account_id, 0));
+        let child = Kitties::<Test>::get(2).unwrap();
+        assert_eq!(child.parents, Some((0, 1)));
+        assert_eq!(child.generation, 1);
+        assert_eq!(Owner::<Test>::get(2), Some(account_id));
+    });
+}
+
+#[test]
+fn commit_breed_failed_not_owner() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        set_gender(0, Gender::Male);
+        set_gender(1, Gender::Female);
+        assert_noop!(SubstrateKitties::commit_breed(Origin::signed(2), 0, 1),Error::<Test>::NotOwner);
+    });
+}
+
+#[test]
+fn reveal_works() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        let commitment_id = 0u64;
+        assert_ok!(SubstrateKitties::commit_create(Origin::signed(account_id)));
+        // 揭示前拥有的Kitty达到上限，自动揭示失败，承诺保留
+        for _ in 0..MaxKittiesOwned::get() {
+            assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        }
+        run_to_block(2 + RevealDelay::get());
+        assert!(Commitments::<Test>::get(commitment_id).is_some());
+        assert_noop!(SubstrateKitties::reveal(Origin::signed(2), commitment_id),Error::<Test>::TooManyOwned);
+        // 腾出位置后任何人都可以揭示，Kitty归承诺者所有
+        assert_ok!(SubstrateKitties::burn(Origin::signed(account_id), 0));
+        let kitty_id = MaxKittiesOwned::get();
+        assert_ok!(SubstrateKitties::reveal(Origin::signed(2), commitment_id));
+        assert_has_event!(Event::<Test>::CommitmentRevealed(account_id, commitment_id));
+        assert_eq!(Owner::<Test>::get(kitty_id), Some(account_id));
+        assert_eq!(Commitments::<Test>::get(commitment_id), None);
+    });
+}
+
+#[test]
+fn commitment_expires_and_refunds() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        let stake = StakeForEachKitty::get();
+        assert_ok!(SubstrateKitties::commit_create(Origin::signed(account_id)));
+        // 揭示前拥有的Kitty达到上限，自动揭示失败
+        for _ in 0..MaxKittiesOwned::get() {
+            assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        }
+        let free = Balances::free_balance(&account_id);
+        run_to_block(1 + RevealDelay::get() + RevealWindow::get());
+        // 超时未揭示，退还质押
+        assert_has_event!(Event::<Test>::CommitmentExpired(account_id, 0));
+        assert_eq!(Commitments::<Test>::get(0), None);
+        assert_eq!(OwnedKitties::<Test>::get(&account_id).len() as u32, MaxKittiesOwned::get());
+        assert_eq!(Balances::free_balance(&account_id), free + stake);
+        assert_eq!(
+            Balances::reserved_balance_named(&KITTY_RESERVE_ID, &account_id),
+            stake * MaxKittiesOwned::get() as u128
+        );
+        assert_noop!(SubstrateKitties::reveal(Origin::signed(account_id), 0),Error::<Test>::CommitmentNotFound);
+    });
+}
+
+#[test]
+fn reveal_failed_parent_transferred() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        let commitment_id = 0u64;
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id)));
+        set_gender(0, Gender::Male);
+        set_gender(1, Gender::Female);
+        assert_ok!(SubstrateKitties::commit_breed(Origin::signed(account_id), 0, 1));
+        // 揭示前转让了父母之一
+        assert_ok!(SubstrateKitties::transfer(Origin::signed(account_id), 2, 1));
+        run_to_block(2 + RevealDelay::get());
+        assert!(Commitments::<Test>::get(commitment_id).is_some());
+        assert_eq!(Kitties::<Test>::get(2), None);
+        assert_noop!(SubstrateKitties::reveal(Origin::signed(account_id), commitment_id),Error::<Test>::NotOwner);
+    });
+}
+
+#[test]
+fn commit_create_failed_too_many_commitments_revealing() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..MaxCommitmentsPerBlock::get() {
+            assert_ok!(SubstrateKitties::commit_create(Origin::signed(1)));
+        }
+        assert_noop!(SubstrateKitties::commit_create(Origin::signed(2)),Error::<Test>::TooManyCommitmentsRevealing);
+    });
+}
+
+#[test]
+fn commit_create_batch_works() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        let stake = StakeForEachKitty::get();
+        assert_ok!(SubstrateKitties::commit_create_batch(Origin::signed(account_id), 2));
+        assert_has_event!(Event::<Test>::BatchCompleted(account_id, 2));
+        assert_eq!(Balances::reserved_balance_named(&KITTY_RESERVE_ID, &account_id), stake * 2);
+        // 超过批量上限
+        assert_noop!(SubstrateKitties::commit_create_batch(Origin::signed(account_id), 4),Error::<Test>::BatchTooLarge);
+        // 同一区块揭示的承诺超过上限时全部回滚
+        assert_noop!(SubstrateKitties::commit_create_batch(Origin::signed(2), 1),Error::<Test>::TooManyCommitmentsRevealing);
+        run_to_block(2 + RevealDelay::get());
+        assert_eq!(OwnedKitties::<Test>::get(account_id).into_inner(), vec![0, 1]);
+        // 同一批次的Kitty的DNA不同
+        assert_ne!(Kitties::<Test>::get(0).unwrap().dna, Kitties::<Test>::get(1).unwrap().dna);
+    });
+}
+
+#[test]
+fn commit_breed_with_sire_works() {
+    new_test_ext().execute_with(|| {
+        let account_id_1: u64 = 1;
+        let account_id_2: u64 = 2;
+        let sire_id = 0u32;
+        let kitty_id = 1u32;
+        let child_id = 2u32;
+        let fee = 500u128;
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id_1)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(account_id_2)));
+        set_gender(sire_id, Gender::Male);
+        set_gender(kitty_id, Gender::Female);
+        assert_noop!(SubstrateKitties::commit_breed_with_sire(Origin::signed(account_id_2), kitty_id, sire_id),Error::<Test>::SireNotOffered);
+        assert_ok!(SubstrateKitties::offer_sire(Origin::signed(account_id_1), sire_id, Some(fee)));
+        let sire_owner_balance = Balances::free_balance(&account_id_1);
+        // 承诺时支付配种费，父母进入冷却期
+        assert_ok!(SubstrateKitties::commit_breed_with_sire(Origin::signed(account_id_2), kitty_id, sire_id));
+        assert_has_event!(Event::<Test>::KittySired(account_id_2, account_id_1, sire_id, fee));
+        assert_eq!(Balances::free_balance(&account_id_1), sire_owner_balance + fee);
+        assert_noop!(SubstrateKitties::commit_breed_with_sire(Origin::signed(account_id_2), kitty_id, sire_id),Error::<Test>::KittyOnCooldown);
+        // 子代在揭示时生成，属于调用者
+        run_to_block(2 + RevealDelay::get());
+        assert_eq!(Kitties::<Test>::get(child_id).unwrap().parents, Some((kitty_id, sire_id)));
+        assert_eq!(Owner::<Test>::get(child_id), Some(account_id_2));
+    });
+}
+
+#[test]
+fn commit_create_failed_not_enough_balance_for_staking() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(SubstrateKitties::commit_create(Origin::signed(5)),Error::<Test>::NotEnoughBalanceForStaking);
+    });
+}
+
+#[test]
+fn create_failed_instant_mint_disabled() {
+    new_test_ext().execute_with(|| {
+        AllowInstantMint::set(&false);
+        assert_noop!(SubstrateKitties::create(Origin::signed(1)),Error::<Test>::InstantMintDisabled);
+        assert_noop!(SubstrateKitties::create_batch(Origin::signed(1), 2),Error::<Test>::InstantMintDisabled);
+        assert_noop!(SubstrateKitties::breed_with_sire(Origin::signed(1), 0, 1),Error::<Test>::InstantMintDisabled);
+        // 仍然可以通过承诺创建
+        assert_ok!(SubstrateKitties::commit_create(Origin::signed(1)));
+        AllowInstantMint::set(&true);
+    });
+}
+
+#[test]
+fn kitty_info_works() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::sell_with_expiry(Origin::signed(1), kitty_id, 5_000, 10));
+        let kitty = Kitties::<Test>::get(kitty_id).unwrap();
+        let info = SubstrateKitties::kitty_info(kitty_id).unwrap();
+        assert_eq!(info.id, kitty_id);
+        assert_eq!(info.owner, 1);
+        assert_eq!(info.dna, kitty.dna);
+        assert_eq!(info.generation, 0);
+        assert_eq!(info.birth_block, 1);
+        assert_eq!(info.traits, genes::KittyTraits::from_dna(&kitty.dna));
+        assert_eq!(info.price, Some(5_000));
+        assert_eq!(info.price_asset, None);
+        assert_eq!(info.listing_expires, Some(10));
+        assert_eq!(info.user, None);
+        assert_eq!(info.lease_end, None);
+        // 出租后返回租用者和租期结束的区块
+        assert_ok!(SubstrateKitties::sell(Origin::signed(1), kitty_id, None));
+        assert_ok!(SubstrateKitties::list_for_rent(Origin::signed(1), kitty_id, 100, 10));
+        assert_ok!(SubstrateKitties::rent(Origin::signed(2), kitty_id, 5));
+        let info = SubstrateKitties::kitty_info(kitty_id).unwrap();
+        assert_eq!(info.owner, 1);
+        assert_eq!(info.user, Some(2));
+        assert_eq!(info.lease_end, Some(6));
+        // 不存在的Kitty
+        assert_eq!(SubstrateKitties::kitty_info(1), None);
+    });
+}
+
+#[test]
+fn kitty_info_serializes_price_as_string() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        let price = u128::max_value();
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(1), kitty_id, Some(price)));
+        let info = SubstrateKitties::kitty_info(kitty_id).unwrap();
+        // u128不能表示为JSON数字，价格以字符串表示
+        let value = serde_json::to_value(&info).unwrap();
+        assert_eq!(value["price"], serde_json::json!(price.to_string()));
+        let decoded: KittyInfoOf<Test> = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded, info);
+        // 未出售时价格为null
+        assert_ok!(SubstrateKitties::sell(Origin::signed(1), kitty_id, None));
+        let value = serde_json::to_value(&SubstrateKitties::kitty_info(kitty_id).unwrap()).unwrap();
+        assert_eq!(value["price"], serde_json::Value::Null);
+        assert_eq!(value["user"], serde_json::Value::Null);
+        assert_eq!(value["leaseEnd"], serde_json::Value::Null);
+        // 出租后包含租用者和租期结束的区块
+        assert_ok!(SubstrateKitties::list_for_rent(Origin::signed(1), kitty_id, 100, 10));
+        assert_ok!(SubstrateKitties::rent(Origin::signed(2), kitty_id, 5));
+        let info = SubstrateKitties::kitty_info(kitty_id).unwrap();
+        let value = serde_json::to_value(&info).unwrap();
+        assert_eq!(value["user"], serde_json::json!(2));
+        assert_eq!(value["leaseEnd"], serde_json::json!(6));
+        let decoded: KittyInfoOf<Test> = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded, info);
+    });
+}
+
+#[test]
+fn kitties_owned_by_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(2)));
+        assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        assert_eq!(SubstrateKitties::kitties_owned_by(&1), vec![0, 2]);
+        assert_eq!(SubstrateKitties::kitties_owned_by(&5), Vec::<u32>::new());
+        assert_eq!(SubstrateKitties::kitty_count(), 3);
+    });
+}
+
+#[test]
+fn listings_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(SubstrateKitties::create_batch(Origin::signed(1), 3));
+        assert_ok!(SubstrateKitties::sell(Origin::signed(1), 0, Some(5_000)));
+        assert_ok!(SubstrateKitties::sell_dutch(Origin::signed(1), 2, 10_000, 1_000, 10));
+        // 取消出售的Kitty不在列表中
+        assert_ok!(SubstrateKitties::sell(Origin::signed(1), 1, None));
+        let mut listed: Vec<(u32, Option<u128>)> =
+            SubstrateKitties::listings(0).into_iter().map(|info| (info.id, info.price)).collect();
+        listed.sort();
+        assert_eq!(listed, vec![(0, Some(5_000)), (2, Some(10_000))]);
+        assert!(SubstrateKitties::listings(1).is_empty());
+    });
+}
+
+#[test]
+fn migrate_to_v1_works() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        let dna = [1u8; 16];
+        // 写入旧版本格式的Kitty
+        frame_support::storage::unhashed::put(&Kitties::<Test>::hashed_key_for(kitty_id), &Some(dna));
+        StorageVersion::new(0).put::<SubstrateKitties>();
+        crate::migrations::v1::migrate::<Test>();
+        assert_eq!(SubstrateKitties::on_chain_storage_version(), 1);
+        assert_eq!(
+            Kitties::<Test>::get(kitty_id),
+            Some(Kitty { dna, parents: None, generation: 0, birth_block: 0, gender: Gender::Female })
+        );
+    });
+}
+
+#[test]
+fn migrate_to_v2_works() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        let kitty_id = 0u32;
+        let stake = StakeForEachKitty::get();
+        // 旧版本使用普通锁定质押
+        Owner::<Test>::insert(kitty_id, Some(account_id));
+        assert_ok!(Balances::reserve(&account_id, stake));
+        StorageVersion::new(1).put::<SubstrateKitties>();
+        crate::migrations::v2::migrate::<Test>();
+        assert_eq!(SubstrateKitties::on_chain_storage_version(), 2);
+        assert_eq!(Balances::reserved_balance_named(&KITTY_RESERVE_ID, &account_id), stake);
+        assert_eq!(Balances::reserved_balance(&account_id), stake);
+        assert_eq!(KittyDeposits::<Test>::get(kitty_id), stake);
+    });
+}
+
+#[test]
+fn migrate_to_v2_indexes_owned_kitties() {
+    new_test_ext().execute_with(|| {
+        let stake = StakeForEachKitty::get();
+        // 旧版本的Kitty没有账户索引
+        for (kitty_id, account_id) in [(0u32, 1u64), (1, 2), (2, 1)] {
+            Owner::<Test>::insert(kitty_id, Some(account_id));
+            assert_ok!(Balances::reserve(&account_id, stake));
+        }
+        KittyCnt::<Test>::put(3);
+        StorageVersion::new(1).put::<SubstrateKitties>();
+        crate::migrations::v2::migrate::<Test>();
+        let mut owned = OwnedKitties::<Test>::get(1).into_inner();
+        owned.sort();
+        assert_eq!(owned, vec![0, 2]);
+        assert_eq!(OwnedKitties::<Test>::get(2).into_inner(), vec![1]);
+        // 迁移后拥有数量上限对旧Kitty同样生效
+        for _ in 0..MaxKittiesOwned::get() - 2 {
+            assert_ok!(SubstrateKitties::create(Origin::signed(1)));
+        }
+        assert_noop!(SubstrateKitties::create(Origin::signed(1)),Error::<Test>::TooManyOwned);
+    });
+}
+
+#[test]
+fn migrate_to_v3_works() {
+    new_test_ext().execute_with(|| {
+        let kitty_id = 0u32;
+        let mut dna = [0u8; 16];
+        dna[genes::EYE_COLOUR] = 0x35;
+        Kitties::<Test>::insert(kitty_id, Some(Kitty { dna, parents: None, generation: 0, birth_block: 0, gender: Gender::Male }));
+        StorageVersion::new(2).put::<SubstrateKitties>();
+        crate::migrations::v3::migrate::<Test>();
+        assert_eq!(SubstrateKitties::on_chain_storage_version(), 3);
+        // 已有的Kitty计入性状统计
+        assert_eq!(TraitCounts::<Test>::get(genes::EYE_COLOUR as u8, 5), 1);
+        assert_eq!(TraitCounts::<Test>::get(genes::BODY_COLOUR as u8, 0), 1);
+    });
+}
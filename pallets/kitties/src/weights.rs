@@ -0,0 +1,850 @@
+//! Hand-written weights for pallet_kitties
+//!
+//! This file is NOT generated by the benchmark CLI. The storage accesses are counted by hand from
+//! the worst case each benchmark sets up, and the base weights are estimates that have not been
+//! measured on reference hardware. Replace this file with the CLI output before relying on it:
+//!
+//! ```sh
+//! ./target/release/node-template benchmark \
+//! 	--chain=dev \
+//! 	--steps=50 \
+//! 	--repeat=20 \
+//! 	--pallet=pallet_kitties \
+//! 	--extrinsic='*' \
+//! 	--execution=wasm \
+//! 	--wasm-execution=compiled \
+//! 	--heap-pages=4096 \
+//! 	--output=./pallets/kitties/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}, sp_std::marker::PhantomData};
+
+/// Weight functions needed for pallet_kitties.
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn create_batch(n: u32, ) -> Weight;
+	fn breed() -> Weight;
+	fn breed_with_sire() -> Weight;
+	fn sell() -> Weight;
+	fn sell_for_asset() -> Weight;
+	fn sell_with_expiry() -> Weight;
+	fn sell_batch(n: u32, ) -> Weight;
+	fn transfer() -> Weight;
+	fn transfer_batch(n: u32, ) -> Weight;
+	fn transfer_from() -> Weight;
+	fn buy() -> Weight;
+	fn buy_at_most() -> Weight;
+	fn buy_with_asset() -> Weight;
+	fn offer_sire() -> Weight;
+	fn commit_create() -> Weight;
+	fn commit_create_batch(n: u32, ) -> Weight;
+	fn commit_breed() -> Weight;
+	fn commit_breed_with_sire() -> Weight;
+	fn reveal() -> Weight;
+	fn cancel_listing() -> Weight;
+	fn burn(o: u32, ) -> Weight;
+	fn sell_dutch() -> Weight;
+	fn gift() -> Weight;
+	fn accept_gift() -> Weight;
+	fn reject_gift() -> Weight;
+	fn list_for_rent() -> Weight;
+	fn cancel_rent_listing() -> Weight;
+	fn rent() -> Weight;
+	fn approve() -> Weight;
+	fn clear_approval() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn make_offer(o: u32, ) -> Weight;
+	fn withdraw_offer() -> Weight;
+	fn accept_offer(o: u32, ) -> Weight;
+	fn create_auction() -> Weight;
+	fn bid() -> Weight;
+	fn cancel_auction() -> Weight;
+}
+
+/// Weights for pallet_kitties using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: SubstrateKitties KittyCnt (r:1 w:1)
+	// Storage: SubstrateKitties OwnedKitties (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SubstrateKitties TraitCounts (r:6 w:6)
+	// Storage: SubstrateKitties KittyDeposits (r:0 w:1)
+	// Storage: SubstrateKitties Kitties (r:0 w:1)
+	// Storage: SubstrateKitties Owner (r:0 w:1)
+	// Storage: SubstrateKitties Creators (r:0 w:1)
+	fn create() -> Weight {
+		(61_184_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: SubstrateKitties KittyCnt (r:1 w:1)
+	// Storage: SubstrateKitties OwnedKitties (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SubstrateKitties TraitCounts (r:6 w:6)
+	// Storage: SubstrateKitties KittyDeposits (r:0 w:1)
+	// Storage: SubstrateKitties Kitties (r:0 w:1)
+	// Storage: SubstrateKitties Owner (r:0 w:1)
+	// Storage: SubstrateKitties Creators (r:0 w:1)
+	fn create_batch(n: u32, ) -> Weight {
+		(6_931_000 as Weight)
+			.saturating_add((57_406_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((14 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: SubstrateKitties Leases (r:2 w:0)
+	// Storage: SubstrateKitties Owner (r:2 w:1)
+	// Storage: SubstrateKitties Kitties (r:2 w:1)
+	// Storage: SubstrateKitties PendingGifts (r:2 w:0)
+	// Storage: SubstrateKitties BreedingStates (r:2 w:2)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: SubstrateKitties KittyCnt (r:1 w:1)
+	// Storage: SubstrateKitties OwnedKitties (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SubstrateKitties TraitCounts (r:6 w:6)
+	// Storage: SubstrateKitties CooldownEnds (r:0 w:2)
+	// Storage: SubstrateKitties KittyDeposits (r:0 w:1)
+	// Storage: SubstrateKitties Creators (r:0 w:1)
+	fn breed() -> Weight {
+		(88_517_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+	}
+	// Storage: SubstrateKitties Leases (r:2 w:0)
+	// Storage: SubstrateKitties Owner (r:2 w:1)
+	// Storage: SubstrateKitties SireListings (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SubstrateKitties Kitties (r:2 w:1)
+	// Storage: SubstrateKitties PendingGifts (r:2 w:0)
+	// Storage: SubstrateKitties BreedingStates (r:2 w:2)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: SubstrateKitties KittyCnt (r:1 w:1)
+	// Storage: SubstrateKitties OwnedKitties (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: SubstrateKitties TraitCounts (r:6 w:6)
+	// Storage: SubstrateKitties CooldownEnds (r:0 w:2)
+	// Storage: SubstrateKitties KittyDeposits (r:0 w:1)
+	// Storage: SubstrateKitties Creators (r:0 w:1)
+	fn breed_with_sire() -> Weight {
+		(111_843_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(23 as Weight))
+			.saturating_add(T::DbWeight::get().writes(20 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	fn sell() -> Weight {
+		(34_906_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	fn sell_for_asset() -> Weight {
+		(39_127_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:2 w:2)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	fn sell_with_expiry() -> Weight {
+		(41_562_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	fn sell_batch(n: u32, ) -> Weight {
+		(3_218_000 as Weight)
+			.saturating_add((33_604_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:1)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SubstrateKitties KittyDeposits (r:1 w:1)
+	// Storage: SubstrateKitties OwnedKitties (r:2 w:2)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn transfer() -> Weight {
+		(71_350_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:1)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SubstrateKitties KittyDeposits (r:1 w:1)
+	// Storage: SubstrateKitties OwnedKitties (r:2 w:2)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn transfer_batch(n: u32, ) -> Weight {
+		(5_104_000 as Weight)
+			.saturating_add((70_218_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((16 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:1)
+	// Storage: SubstrateKitties Approvals (r:1 w:1)
+	// Storage: SubstrateKitties Operators (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SubstrateKitties KittyDeposits (r:1 w:1)
+	// Storage: SubstrateKitties OwnedKitties (r:2 w:2)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn transfer_from() -> Weight {
+		(76_935_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:1 w:1)
+	// Storage: SubstrateKitties DutchListings (r:1 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:1 w:1)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:3 w:3)
+	// Storage: SubstrateKitties KittyDeposits (r:1 w:1)
+	// Storage: SubstrateKitties Creators (r:1 w:0)
+	// Storage: SubstrateKitties OwnedKitties (r:2 w:2)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn buy() -> Weight {
+		(104_722_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:1 w:1)
+	// Storage: SubstrateKitties DutchListings (r:1 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:1 w:1)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:3 w:3)
+	// Storage: SubstrateKitties KittyDeposits (r:1 w:1)
+	// Storage: SubstrateKitties Creators (r:1 w:0)
+	// Storage: SubstrateKitties OwnedKitties (r:2 w:2)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn buy_at_most() -> Weight {
+		(105_371_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:1 w:1)
+	// Storage: SubstrateKitties DutchListings (r:1 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:1 w:1)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SubstrateKitties KittyDeposits (r:1 w:1)
+	// Storage: SubstrateKitties Creators (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:4 w:4)
+	// Storage: SubstrateKitties OwnedKitties (r:2 w:2)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn buy_with_asset() -> Weight {
+		(142_608_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(21 as Weight))
+	}
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn offer_sire() -> Weight {
+		(20_314_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SubstrateKitties NextCommitmentId (r:1 w:1)
+	// Storage: SubstrateKitties CommitmentsRevealingAt (r:1 w:1)
+	// Storage: SubstrateKitties CommitmentsExpiringAt (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SubstrateKitties Commitments (r:0 w:1)
+	fn commit_create() -> Weight {
+		(35_861_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: SubstrateKitties NextCommitmentId (r:1 w:1)
+	// Storage: SubstrateKitties CommitmentsRevealingAt (r:1 w:1)
+	// Storage: SubstrateKitties CommitmentsExpiringAt (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SubstrateKitties Commitments (r:0 w:1)
+	fn commit_create_batch(n: u32, ) -> Weight {
+		(4_612_000 as Weight)
+			.saturating_add((31_254_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: SubstrateKitties Leases (r:2 w:0)
+	// Storage: SubstrateKitties Owner (r:2 w:0)
+	// Storage: SubstrateKitties Kitties (r:2 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:2 w:0)
+	// Storage: SubstrateKitties BreedingStates (r:2 w:2)
+	// Storage: SubstrateKitties NextCommitmentId (r:1 w:1)
+	// Storage: SubstrateKitties CommitmentsRevealingAt (r:1 w:1)
+	// Storage: SubstrateKitties CommitmentsExpiringAt (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SubstrateKitties CooldownEnds (r:0 w:2)
+	// Storage: SubstrateKitties Commitments (r:0 w:1)
+	fn commit_breed() -> Weight {
+		(54_480_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: SubstrateKitties Leases (r:2 w:0)
+	// Storage: SubstrateKitties Owner (r:2 w:0)
+	// Storage: SubstrateKitties SireListings (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SubstrateKitties Kitties (r:2 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:2 w:0)
+	// Storage: SubstrateKitties BreedingStates (r:2 w:2)
+	// Storage: SubstrateKitties NextCommitmentId (r:1 w:1)
+	// Storage: SubstrateKitties CommitmentsRevealingAt (r:1 w:1)
+	// Storage: SubstrateKitties CommitmentsExpiringAt (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: SubstrateKitties CooldownEnds (r:0 w:2)
+	// Storage: SubstrateKitties Commitments (r:0 w:1)
+	fn commit_breed_with_sire() -> Weight {
+		(78_413_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: SubstrateKitties Commitments (r:1 w:1)
+	// Storage: SubstrateKitties CommitmentsExpiringAt (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SubstrateKitties Leases (r:2 w:0)
+	// Storage: SubstrateKitties Owner (r:2 w:1)
+	// Storage: SubstrateKitties Kitties (r:2 w:1)
+	// Storage: SubstrateKitties KittyCnt (r:1 w:1)
+	// Storage: SubstrateKitties OwnedKitties (r:1 w:1)
+	// Storage: SubstrateKitties TraitCounts (r:6 w:6)
+	// Storage: SubstrateKitties KittyDeposits (r:0 w:1)
+	// Storage: SubstrateKitties Creators (r:0 w:1)
+	fn reveal() -> Weight {
+		(90_547_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties ListForSale (r:1 w:1)
+	// Storage: SubstrateKitties DutchListings (r:1 w:1)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	fn cancel_listing() -> Weight {
+		(30_538_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:1)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties Kitties (r:1 w:1)
+	// Storage: SubstrateKitties TraitCounts (r:6 w:6)
+	// Storage: SubstrateKitties OwnedKitties (r:1 w:1)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties Offers (r:1 w:1)
+	// Storage: SubstrateKitties OffersExpiringAt (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: SubstrateKitties BreedingStates (r:1 w:1)
+	// Storage: SubstrateKitties KittyDeposits (r:1 w:1)
+	// Storage: SubstrateKitties Creators (r:0 w:1)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	// Storage: SubstrateKitties CooldownEnds (r:0 w:1)
+	fn burn(o: u32, ) -> Weight {
+		(85_260_000 as Weight)
+			.saturating_add((18_147_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(20 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes(25 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	fn sell_dutch() -> Weight {
+		(33_815_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:1)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties GiftsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	fn gift() -> Weight {
+		(40_127_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: SubstrateKitties PendingGifts (r:1 w:1)
+	// Storage: SubstrateKitties GiftsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties Owner (r:1 w:1)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SubstrateKitties KittyDeposits (r:1 w:1)
+	// Storage: SubstrateKitties OwnedKitties (r:2 w:2)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn accept_gift() -> Weight {
+		(78_904_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+	}
+	// Storage: SubstrateKitties PendingGifts (r:1 w:1)
+	// Storage: SubstrateKitties GiftsExpiringAt (r:1 w:1)
+	fn reject_gift() -> Weight {
+		(27_386_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	fn list_for_rent() -> Weight {
+		(24_650_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties RentalListings (r:1 w:1)
+	fn cancel_rent_listing() -> Weight {
+		(21_733_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties RentalListings (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SubstrateKitties LeasesExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn rent() -> Weight {
+		(62_418_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	fn approve() -> Weight {
+		(19_208_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	fn clear_approval() -> Weight {
+		(18_671_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SubstrateKitties Operators (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		(15_542_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Offers (r:1 w:1)
+	// Storage: SubstrateKitties OffersExpiringAt (r:2 w:2)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn make_offer(o: u32, ) -> Weight {
+		(38_716_000 as Weight)
+			.saturating_add((15_292_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+	}
+	// Storage: SubstrateKitties Offers (r:1 w:1)
+	// Storage: SubstrateKitties OffersExpiringAt (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_offer() -> Weight {
+		(32_105_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:1)
+	// Storage: SubstrateKitties Auctions (r:1 w:0)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties Offers (r:1 w:1)
+	// Storage: SubstrateKitties OffersExpiringAt (r:1 w:1)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:3 w:3)
+	// Storage: SubstrateKitties KittyDeposits (r:1 w:1)
+	// Storage: SubstrateKitties Creators (r:1 w:0)
+	// Storage: SubstrateKitties OwnedKitties (r:2 w:2)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties Approvals (r:0 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	// Storage: SubstrateKitties RentalListings (r:0 w:1)
+	// Storage: SubstrateKitties SireListings (r:0 w:1)
+	fn accept_offer(o: u32, ) -> Weight {
+		(112_574_000 as Weight)
+			.saturating_add((16_318_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+	}
+	// Storage: SubstrateKitties Owner (r:1 w:0)
+	// Storage: SubstrateKitties Auctions (r:1 w:1)
+	// Storage: SubstrateKitties PendingGifts (r:1 w:0)
+	// Storage: SubstrateKitties Leases (r:1 w:0)
+	// Storage: SubstrateKitties AuctionsEndingAt (r:1 w:1)
+	// Storage: SubstrateKitties ListingExpiry (r:1 w:1)
+	// Storage: SubstrateKitties ListingsExpiringAt (r:1 w:1)
+	// Storage: SubstrateKitties ListForSale (r:0 w:1)
+	// Storage: SubstrateKitties ListingAsset (r:0 w:1)
+	// Storage: SubstrateKitties DutchListings (r:0 w:1)
+	fn create_auction() -> Weight {
+		(41_962_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: SubstrateKitties Auctions (r:1 w:1)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	fn bid() -> Weight {
+		(52_847_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: SubstrateKitties Auctions (r:1 w:1)
+	// Storage: SubstrateKitties AuctionsEndingAt (r:1 w:1)
+	fn cancel_auction() -> Weight {
+		(26_519_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create() -> Weight {
+		(61_184_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	fn create_batch(n: u32, ) -> Weight {
+		(6_931_000 as Weight)
+			.saturating_add((57_406_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((14 as Weight).saturating_mul(n as Weight)))
+	}
+	fn breed() -> Weight {
+		(88_517_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
+	}
+	fn breed_with_sire() -> Weight {
+		(111_843_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(23 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(20 as Weight))
+	}
+	fn sell() -> Weight {
+		(34_906_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn sell_for_asset() -> Weight {
+		(39_127_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn sell_with_expiry() -> Weight {
+		(41_562_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn sell_batch(n: u32, ) -> Weight {
+		(3_218_000 as Weight)
+			.saturating_add((33_604_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer() -> Weight {
+		(71_350_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn transfer_batch(n: u32, ) -> Weight {
+		(5_104_000 as Weight)
+			.saturating_add((70_218_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((16 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer_from() -> Weight {
+		(76_935_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn buy() -> Weight {
+		(104_722_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+	}
+	fn buy_at_most() -> Weight {
+		(105_371_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+	}
+	fn buy_with_asset() -> Weight {
+		(142_608_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(21 as Weight))
+	}
+	fn offer_sire() -> Weight {
+		(20_314_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn commit_create() -> Weight {
+		(35_861_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn commit_create_batch(n: u32, ) -> Weight {
+		(4_612_000 as Weight)
+			.saturating_add((31_254_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn commit_breed() -> Weight {
+		(54_480_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn commit_breed_with_sire() -> Weight {
+		(78_413_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn reveal() -> Weight {
+		(90_547_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn cancel_listing() -> Weight {
+		(30_538_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn burn(o: u32, ) -> Weight {
+		(85_260_000 as Weight)
+			.saturating_add((18_147_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(20 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(25 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+	}
+	fn sell_dutch() -> Weight {
+		(33_815_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn gift() -> Weight {
+		(40_127_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn accept_gift() -> Weight {
+		(78_904_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
+	}
+	fn reject_gift() -> Weight {
+		(27_386_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn list_for_rent() -> Weight {
+		(24_650_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_rent_listing() -> Weight {
+		(21_733_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn rent() -> Weight {
+		(62_418_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn approve() -> Weight {
+		(19_208_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn clear_approval() -> Weight {
+		(18_671_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_approval_for_all() -> Weight {
+		(15_542_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn make_offer(o: u32, ) -> Weight {
+		(38_716_000 as Weight)
+			.saturating_add((15_292_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+	}
+	fn withdraw_offer() -> Weight {
+		(32_105_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn accept_offer(o: u32, ) -> Weight {
+		(112_574_000 as Weight)
+			.saturating_add((16_318_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+	}
+	fn create_auction() -> Weight {
+		(41_962_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn bid() -> Weight {
+		(52_847_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn cancel_auction() -> Weight {
+		(26_519_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
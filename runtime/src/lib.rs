@@ -14,7 +14,9 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
     create_runtime_str, generic, impl_opaque_keys,
-    traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
+    traits::{
+        AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify,
+    },
     transaction_validity::{TransactionSource, TransactionValidity},
     ApplyExtrinsicResult, MultiSignature,
 };
@@ -31,7 +33,7 @@ pub use frame_support::{
         constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
         IdentityFee, Weight,
     },
-    StorageValue,
+    PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -59,6 +61,12 @@ pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::Account
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Index of a kitty.
+pub type KittyIndex = u32;
+
+/// Identifier of an asset in pallet-assets.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -101,10 +109,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 100,
+    spec_version: 101,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -243,11 +251,12 @@ impl pallet_timestamp::Config for Runtime {
 parameter_types! {
 	pub const ExistentialDeposit: u128 = 500;
 	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
 }
 
 impl pallet_balances::Config for Runtime {
     type MaxLocks = MaxLocks;
-    type MaxReserves = ();
+    type MaxReserves = MaxReserves;
     type ReserveIdentifier = [u8; 8];
     /// The type for recording an account's balance.
     type Balance = Balance;
@@ -277,6 +286,30 @@ impl pallet_sudo::Config for Runtime {
     type Call = Call;
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 10_000;
+	pub const ApprovalDeposit: Balance = 500;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 1_000;
+	pub const MetadataDepositPerByte: Balance = 10;
+}
+
+impl pallet_assets::Config for Runtime {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = AssetId;
+    type Currency = Balances;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = StringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
     type Event = Event;
@@ -284,6 +317,26 @@ impl pallet_template::Config for Runtime {
 
 parameter_types! {
 	pub const StakeForEachKitty: u128 = 1_000;
+	pub const MaxKittiesOwned: u32 = 100;
+	pub const BreedCooldown: BlockNumber = 10 * MINUTES;
+	pub const MaxAuctionsPerBlock: u32 = 10;
+	pub const MaxOffersPerKitty: u32 = 20;
+	pub const MarketplaceFee: Permill = Permill::from_percent(2);
+	pub const CreatorRoyalty: Permill = Permill::from_percent(5);
+	pub const GiftExpiry: BlockNumber = 1 * DAYS;
+	pub const MaxGiftsPerBlock: u32 = 10;
+	pub const MaxLeasesPerBlock: u32 = 10;
+	pub const MaxBatchSize: u32 = 50;
+	pub const MaxListingsPerBlock: u32 = 50;
+	// 以资产支付的市场手续费的收款账户
+	pub AssetFeeCollector: AccountId = PalletId(*b"py/kfees").into_account();
+	pub const MutationChance: Permill = Permill::from_percent(5);
+	pub const RevealDelay: BlockNumber = 2;
+	pub const RevealWindow: BlockNumber = 1 * HOURS;
+	pub const MaxCommitmentsPerBlock: u32 = 50;
+	// 保留直接创建和繁殖，不破坏已有的调用，需要防止DNA被预测时使用承诺和揭示
+	pub const AllowInstantMint: bool = true;
+	pub const MaxOffersPerBlock: u32 = 50;
 }
 
 /// Configure the pallet-kitties in pallets/kitties.
@@ -292,7 +345,29 @@ impl pallet_kitties::Config for Runtime {
     type Currency = Balances;
     type Randomness = RandomnessCollectiveFlip;
     type StakeForEachKitty = StakeForEachKitty;
-    type KittyIndex = u32;
+    type KittyIndex = KittyIndex;
+    type MaxKittiesOwned = MaxKittiesOwned;
+    type BreedCooldown = BreedCooldown;
+    type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+    type MaxOffersPerKitty = MaxOffersPerKitty;
+    type MarketplaceFee = MarketplaceFee;
+    type CreatorRoyalty = CreatorRoyalty;
+    // 市场手续费直接销毁
+    type OnMarketplaceFee = ();
+    type GiftExpiry = GiftExpiry;
+    type MaxGiftsPerBlock = MaxGiftsPerBlock;
+    type MaxLeasesPerBlock = MaxLeasesPerBlock;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxListingsPerBlock = MaxListingsPerBlock;
+    type Assets = Assets;
+    type AssetFeeCollector = AssetFeeCollector;
+    type MutationChance = MutationChance;
+    type RevealDelay = RevealDelay;
+    type RevealWindow = RevealWindow;
+    type MaxCommitmentsPerBlock = MaxCommitmentsPerBlock;
+    type AllowInstantMint = AllowInstantMint;
+    type MaxOffersPerBlock = MaxOffersPerBlock;
+    type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -313,6 +388,8 @@ construct_runtime!(
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
 		SubstrateKitties: pallet_kitties,
+		// Appended last so the indices of the pallets above stay unchanged.
+		Assets: pallet_assets,
 	}
 );
 
@@ -474,6 +551,42 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_runtime_api::KittiesApi<Block, AccountId, KittyIndex, Balance, BlockNumber, AssetId>
+		for Runtime
+	{
+		fn kitty_traits(kitty_id: KittyIndex) -> Option<pallet_kitties_runtime_api::KittyTraits> {
+			SubstrateKitties::kitty_traits(kitty_id)
+		}
+
+		fn trait_counts(gene: u8) -> Vec<u32> {
+			SubstrateKitties::trait_counts(gene)
+		}
+
+		fn rarity_score(kitty_id: KittyIndex) -> Option<u32> {
+			SubstrateKitties::rarity_score(kitty_id)
+		}
+
+		fn kitty(
+			kitty_id: KittyIndex,
+		) -> Option<pallet_kitties_runtime_api::KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId>> {
+			SubstrateKitties::kitty_info(kitty_id)
+		}
+
+		fn kitties_owned_by(account: AccountId) -> Vec<KittyIndex> {
+			SubstrateKitties::kitties_owned_by(&account)
+		}
+
+		fn listings(
+			page: u32,
+		) -> Vec<pallet_kitties_runtime_api::KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId>> {
+			SubstrateKitties::listings(page)
+		}
+
+		fn kitty_count() -> KittyIndex {
+			SubstrateKitties::kitty_count()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -492,6 +605,7 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_balances, Balances);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
 			list_benchmark!(list, extra, pallet_template, TemplateModule);
+			list_benchmark!(list, extra, pallet_kitties, SubstrateKitties);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -530,6 +644,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_kitties, SubstrateKitties);
 
 			Ok(batches)
 		}